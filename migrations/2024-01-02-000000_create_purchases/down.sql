@@ -0,0 +1,10 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_purchases_status;
+DROP INDEX IF EXISTS idx_purchases_seller;
+DROP INDEX IF EXISTS idx_purchases_buyer;
+DROP INDEX IF EXISTS idx_purchase_events_checkpoint;
+DROP INDEX IF EXISTS idx_purchase_events_purchase_id;
+
+-- Drop tables
+DROP TABLE IF EXISTS purchases;
+DROP TABLE IF EXISTS purchase_events;
@@ -0,0 +1,40 @@
+-- Create table for storing events from the Purchase module
+CREATE TABLE IF NOT EXISTS purchase_events (
+    id BIGSERIAL PRIMARY KEY,
+    event_type VARCHAR(255) NOT NULL,
+    purchase_id VARCHAR(255) NOT NULL,
+    datapod_id VARCHAR(255),
+    buyer VARCHAR(255) NOT NULL,
+    seller VARCHAR(255),
+    price_sui BIGINT,
+    transaction_digest VARCHAR(255) NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    event_index BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(transaction_digest, event_index)
+);
+
+-- Create table for storing the current state of each purchase
+CREATE TABLE IF NOT EXISTS purchases (
+    purchase_id VARCHAR(255) PRIMARY KEY,
+    datapod_id VARCHAR(255),
+    buyer VARCHAR(255) NOT NULL,
+    seller VARCHAR(255),
+    price_sui BIGINT,
+    status VARCHAR(32) NOT NULL,
+    created_checkpoint BIGINT,
+    created_timestamp BIGINT,
+    completed_timestamp BIGINT,
+    refunded_timestamp BIGINT,
+    disputed_timestamp BIGINT,
+    last_updated_checkpoint BIGINT NOT NULL,
+    last_updated_timestamp BIGINT NOT NULL
+);
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_purchase_events_purchase_id ON purchase_events(purchase_id);
+CREATE INDEX IF NOT EXISTS idx_purchase_events_checkpoint ON purchase_events(checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_purchases_buyer ON purchases(buyer);
+CREATE INDEX IF NOT EXISTS idx_purchases_seller ON purchases(seller);
+CREATE INDEX IF NOT EXISTS idx_purchases_status ON purchases(status);
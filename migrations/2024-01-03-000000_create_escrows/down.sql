@@ -0,0 +1,12 @@
+-- Drop functions
+DROP FUNCTION IF EXISTS pending_escrow_amount(BIGINT);
+
+-- Drop indexes
+DROP INDEX IF EXISTS idx_escrows_status;
+DROP INDEX IF EXISTS idx_escrows_purchase_id;
+DROP INDEX IF EXISTS idx_escrow_events_checkpoint;
+DROP INDEX IF EXISTS idx_escrow_events_escrow_id;
+
+-- Drop tables
+DROP TABLE IF EXISTS escrows;
+DROP TABLE IF EXISTS escrow_events;
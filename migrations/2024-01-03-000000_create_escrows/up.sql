@@ -0,0 +1,49 @@
+-- Create table for storing events from the Escrow module
+CREATE TABLE IF NOT EXISTS escrow_events (
+    id BIGSERIAL PRIMARY KEY,
+    event_type VARCHAR(255) NOT NULL,
+    escrow_id VARCHAR(255) NOT NULL,
+    purchase_id VARCHAR(255),
+    buyer VARCHAR(255),
+    seller VARCHAR(255),
+    amount BIGINT NOT NULL,
+    transaction_digest VARCHAR(255) NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    event_index BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(transaction_digest, event_index)
+);
+
+-- Create table for storing the current state of each escrow
+CREATE TABLE IF NOT EXISTS escrows (
+    escrow_id VARCHAR(255) PRIMARY KEY,
+    purchase_id VARCHAR(255),
+    buyer VARCHAR(255),
+    seller VARCHAR(255),
+    amount BIGINT NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    created_checkpoint BIGINT,
+    created_timestamp BIGINT,
+    released_checkpoint BIGINT,
+    released_timestamp BIGINT,
+    refunded_checkpoint BIGINT,
+    refunded_timestamp BIGINT,
+    last_updated_checkpoint BIGINT NOT NULL,
+    last_updated_timestamp BIGINT NOT NULL
+);
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_escrow_events_escrow_id ON escrow_events(escrow_id);
+CREATE INDEX IF NOT EXISTS idx_escrow_events_checkpoint ON escrow_events(checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_escrows_purchase_id ON escrows(purchase_id);
+CREATE INDEX IF NOT EXISTS idx_escrows_status ON escrows(status);
+
+-- Total amount (in MIST) held in escrows that were pending as of the given checkpoint
+CREATE OR REPLACE FUNCTION pending_escrow_amount(at_checkpoint BIGINT)
+RETURNS NUMERIC AS $$
+    SELECT COALESCE(SUM(amount), 0)
+    FROM escrows
+    WHERE created_checkpoint <= at_checkpoint
+      AND COALESCE(released_checkpoint, refunded_checkpoint, at_checkpoint + 1) > at_checkpoint;
+$$ LANGUAGE SQL STABLE;
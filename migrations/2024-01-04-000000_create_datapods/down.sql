@@ -0,0 +1,7 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_datapods_status;
+DROP INDEX IF EXISTS idx_datapods_category;
+DROP INDEX IF EXISTS idx_datapods_seller;
+
+-- Drop tables
+DROP TABLE IF EXISTS datapods;
@@ -0,0 +1,20 @@
+-- Create table for storing the current state of each DataPod listing
+CREATE TABLE IF NOT EXISTS datapods (
+    datapod_id VARCHAR(255) PRIMARY KEY,
+    seller VARCHAR(255),
+    title VARCHAR(1024),
+    category VARCHAR(255),
+    price_sui BIGINT,
+    kiosk_id VARCHAR(255),
+    status VARCHAR(32),
+    created_checkpoint BIGINT,
+    published_checkpoint BIGINT,
+    last_updated_checkpoint BIGINT NOT NULL,
+    last_event_index BIGINT NOT NULL,
+    last_updated_timestamp BIGINT NOT NULL
+);
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_datapods_seller ON datapods(seller);
+CREATE INDEX IF NOT EXISTS idx_datapods_category ON datapods(category);
+CREATE INDEX IF NOT EXISTS idx_datapods_status ON datapods(status);
@@ -0,0 +1,10 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_smart_contract_objects_live_owner;
+DROP INDEX IF EXISTS idx_smart_contract_objects_live_type;
+
+-- Drop columns
+ALTER TABLE smart_contract_objects
+    DROP COLUMN IF EXISTS deleted_transaction_digest,
+    DROP COLUMN IF EXISTS deleted_at_checkpoint,
+    DROP COLUMN IF EXISTS deletion_kind,
+    DROP COLUMN IF EXISTS is_deleted;
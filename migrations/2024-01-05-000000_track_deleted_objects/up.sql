@@ -0,0 +1,12 @@
+-- Track objects that were deleted or wrapped so they no longer look live
+ALTER TABLE smart_contract_objects
+    ADD COLUMN IF NOT EXISTS is_deleted BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS deletion_kind VARCHAR(32),
+    ADD COLUMN IF NOT EXISTS deleted_at_checkpoint BIGINT,
+    ADD COLUMN IF NOT EXISTS deleted_transaction_digest VARCHAR(255);
+
+-- Create indexes for better query performance on live objects
+CREATE INDEX IF NOT EXISTS idx_smart_contract_objects_live_type
+    ON smart_contract_objects(object_type) WHERE NOT is_deleted;
+CREATE INDEX IF NOT EXISTS idx_smart_contract_objects_live_owner
+    ON smart_contract_objects(owner) WHERE NOT is_deleted;
@@ -0,0 +1,7 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_object_versions_transaction;
+DROP INDEX IF EXISTS idx_object_versions_checkpoint;
+DROP INDEX IF EXISTS idx_object_versions_type;
+
+-- Drop tables
+DROP TABLE IF EXISTS object_versions;
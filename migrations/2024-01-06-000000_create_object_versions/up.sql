@@ -0,0 +1,19 @@
+-- Create table for storing every version of SourceNet objects
+CREATE TABLE IF NOT EXISTS object_versions (
+    object_id VARCHAR(255) NOT NULL,
+    version BIGINT NOT NULL,
+    digest VARCHAR(255) NOT NULL,
+    object_type VARCHAR(1024) NOT NULL,
+    owner VARCHAR(255),
+    data JSONB,
+    deletion_kind VARCHAR(32),
+    transaction_digest VARCHAR(255) NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (object_id, version)
+);
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_object_versions_type ON object_versions(object_type);
+CREATE INDEX IF NOT EXISTS idx_object_versions_checkpoint ON object_versions(checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_object_versions_transaction ON object_versions(transaction_digest);
@@ -0,0 +1,15 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_transaction_digests_status;
+DROP INDEX IF EXISTS idx_transaction_digests_sender;
+
+-- Drop columns
+ALTER TABLE transaction_digests
+    DROP COLUMN IF EXISTS transaction_index,
+    DROP COLUMN IF EXISTS timestamp,
+    DROP COLUMN IF EXISTS non_refundable_storage_fee,
+    DROP COLUMN IF EXISTS storage_rebate,
+    DROP COLUMN IF EXISTS storage_cost,
+    DROP COLUMN IF EXISTS computation_cost,
+    DROP COLUMN IF EXISTS gas_budget,
+    DROP COLUMN IF EXISTS execution_status,
+    DROP COLUMN IF EXISTS sender;
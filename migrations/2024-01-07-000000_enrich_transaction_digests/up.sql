@@ -0,0 +1,16 @@
+-- Record sender, outcome, gas and position of each transaction.
+-- Columns are nullable because rows indexed before this migration do not have them.
+ALTER TABLE transaction_digests
+    ADD COLUMN IF NOT EXISTS sender VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS execution_status VARCHAR(32),
+    ADD COLUMN IF NOT EXISTS gas_budget BIGINT,
+    ADD COLUMN IF NOT EXISTS computation_cost BIGINT,
+    ADD COLUMN IF NOT EXISTS storage_cost BIGINT,
+    ADD COLUMN IF NOT EXISTS storage_rebate BIGINT,
+    ADD COLUMN IF NOT EXISTS non_refundable_storage_fee BIGINT,
+    ADD COLUMN IF NOT EXISTS timestamp BIGINT,
+    ADD COLUMN IF NOT EXISTS transaction_index BIGINT;
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_sender ON transaction_digests(sender);
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_status ON transaction_digests(execution_status);
@@ -0,0 +1,6 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_move_calls_checkpoint;
+DROP INDEX IF EXISTS idx_move_calls_function;
+
+-- Drop tables
+DROP TABLE IF EXISTS move_calls;
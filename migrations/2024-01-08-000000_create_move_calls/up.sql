@@ -0,0 +1,18 @@
+-- Create table for storing Move calls made by SourceNet transactions
+CREATE TABLE IF NOT EXISTS move_calls (
+    transaction_digest VARCHAR(255) NOT NULL,
+    command_index BIGINT NOT NULL,
+    package VARCHAR(255) NOT NULL,
+    module VARCHAR(255) NOT NULL,
+    function VARCHAR(255) NOT NULL,
+    type_arguments JSONB NOT NULL,
+    sender VARCHAR(255) NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (transaction_digest, command_index)
+);
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_move_calls_function ON move_calls(package, module, function);
+CREATE INDEX IF NOT EXISTS idx_move_calls_checkpoint ON move_calls(checkpoint_sequence_number);
@@ -0,0 +1,7 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_failed_transactions_checkpoint;
+DROP INDEX IF EXISTS idx_failed_transactions_abort;
+DROP INDEX IF EXISTS idx_failed_transactions_sender;
+
+-- Drop tables
+DROP TABLE IF EXISTS failed_transactions;
@@ -0,0 +1,19 @@
+-- Create table for storing failed SourceNet transactions and why they failed
+CREATE TABLE IF NOT EXISTS failed_transactions (
+    transaction_digest VARCHAR(255) PRIMARY KEY,
+    sender VARCHAR(255) NOT NULL,
+    error TEXT NOT NULL,
+    failed_command_index BIGINT,
+    abort_module VARCHAR(255),
+    abort_function VARCHAR(255),
+    abort_code BIGINT,
+    abort_error_name VARCHAR(255),
+    checkpoint_sequence_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_failed_transactions_sender ON failed_transactions(sender);
+CREATE INDEX IF NOT EXISTS idx_failed_transactions_abort ON failed_transactions(abort_module, abort_error_name);
+CREATE INDEX IF NOT EXISTS idx_failed_transactions_checkpoint ON failed_transactions(checkpoint_sequence_number);
@@ -0,0 +1,6 @@
+-- Drop indexes
+DROP INDEX IF EXISTS idx_outbox_checkpoint;
+
+-- Drop tables
+DROP TABLE IF EXISTS outbox_consumer_offsets;
+DROP TABLE IF EXISTS outbox;
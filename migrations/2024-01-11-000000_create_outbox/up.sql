@@ -0,0 +1,24 @@
+-- Create outbox of every decoded SourceNet event, in the order the events were committed
+CREATE TABLE IF NOT EXISTS outbox (
+    sequence BIGSERIAL PRIMARY KEY,
+    module VARCHAR(64) NOT NULL,
+    event_type VARCHAR(255) NOT NULL,
+    entity_id VARCHAR(255) NOT NULL,
+    transaction_digest VARCHAR(255) NOT NULL,
+    event_index BIGINT NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (transaction_digest, event_index)
+);
+
+-- Create table for storing the last sequence each consumer group acknowledged
+CREATE TABLE IF NOT EXISTS outbox_consumer_offsets (
+    consumer_group VARCHAR(255) PRIMARY KEY,
+    acked_sequence BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create indexes for better query performance
+CREATE INDEX IF NOT EXISTS idx_outbox_checkpoint ON outbox(checkpoint_sequence_number);
@@ -0,0 +1,36 @@
+-- Fails if any stored value exceeds BIGINT
+ALTER TABLE datapod_events
+    ALTER COLUMN price_sui TYPE BIGINT,
+    ALTER COLUMN old_price TYPE BIGINT,
+    ALTER COLUMN new_price TYPE BIGINT;
+
+ALTER TABLE datapods
+    ALTER COLUMN price_sui TYPE BIGINT;
+
+ALTER TABLE purchase_events
+    ALTER COLUMN price_sui TYPE BIGINT;
+
+ALTER TABLE purchases
+    ALTER COLUMN price_sui TYPE BIGINT;
+
+ALTER TABLE escrow_events
+    ALTER COLUMN amount TYPE BIGINT;
+
+ALTER TABLE escrows
+    ALTER COLUMN amount TYPE BIGINT;
+
+ALTER TABLE smart_contract_objects
+    ALTER COLUMN version TYPE BIGINT;
+
+ALTER TABLE object_versions
+    ALTER COLUMN version TYPE BIGINT;
+
+ALTER TABLE transaction_digests
+    ALTER COLUMN gas_budget TYPE BIGINT,
+    ALTER COLUMN computation_cost TYPE BIGINT,
+    ALTER COLUMN storage_cost TYPE BIGINT,
+    ALTER COLUMN storage_rebate TYPE BIGINT,
+    ALTER COLUMN non_refundable_storage_fee TYPE BIGINT;
+
+ALTER TABLE failed_transactions
+    ALTER COLUMN abort_code TYPE BIGINT;
@@ -0,0 +1,37 @@
+-- Move amounts, gas and versions are u64, which overflows BIGINT above 2^63 - 1.
+-- NUMERIC(20,0) holds every u64 exactly.
+ALTER TABLE datapod_events
+    ALTER COLUMN price_sui TYPE NUMERIC(20, 0),
+    ALTER COLUMN old_price TYPE NUMERIC(20, 0),
+    ALTER COLUMN new_price TYPE NUMERIC(20, 0);
+
+ALTER TABLE datapods
+    ALTER COLUMN price_sui TYPE NUMERIC(20, 0);
+
+ALTER TABLE purchase_events
+    ALTER COLUMN price_sui TYPE NUMERIC(20, 0);
+
+ALTER TABLE purchases
+    ALTER COLUMN price_sui TYPE NUMERIC(20, 0);
+
+ALTER TABLE escrow_events
+    ALTER COLUMN amount TYPE NUMERIC(20, 0);
+
+ALTER TABLE escrows
+    ALTER COLUMN amount TYPE NUMERIC(20, 0);
+
+ALTER TABLE smart_contract_objects
+    ALTER COLUMN version TYPE NUMERIC(20, 0);
+
+ALTER TABLE object_versions
+    ALTER COLUMN version TYPE NUMERIC(20, 0);
+
+ALTER TABLE transaction_digests
+    ALTER COLUMN gas_budget TYPE NUMERIC(20, 0),
+    ALTER COLUMN computation_cost TYPE NUMERIC(20, 0),
+    ALTER COLUMN storage_cost TYPE NUMERIC(20, 0),
+    ALTER COLUMN storage_rebate TYPE NUMERIC(20, 0),
+    ALTER COLUMN non_refundable_storage_fee TYPE NUMERIC(20, 0);
+
+ALTER TABLE failed_transactions
+    ALTER COLUMN abort_code TYPE NUMERIC(20, 0);
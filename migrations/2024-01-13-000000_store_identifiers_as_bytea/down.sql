@@ -0,0 +1,36 @@
+-- Drop views
+DROP VIEW IF EXISTS transaction_digests_text;
+DROP VIEW IF EXISTS object_versions_text;
+DROP VIEW IF EXISTS smart_contract_objects_text;
+DROP VIEW IF EXISTS datapod_events_text;
+
+-- Restore text identifiers
+ALTER TABLE transaction_digests
+    ALTER COLUMN tx_digest TYPE VARCHAR(255) USING sui_digest_text(tx_digest),
+    ALTER COLUMN sender TYPE VARCHAR(255) USING sui_address_text(sender);
+
+ALTER TABLE object_versions
+    ALTER COLUMN object_id TYPE VARCHAR(255) USING sui_address_text(object_id),
+    ALTER COLUMN digest TYPE VARCHAR(255) USING sui_digest_text(digest),
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest);
+
+ALTER TABLE smart_contract_objects
+    ALTER COLUMN object_id TYPE VARCHAR(255) USING sui_address_text(object_id),
+    ALTER COLUMN digest TYPE VARCHAR(255) USING sui_digest_text(digest),
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest),
+    ALTER COLUMN deleted_transaction_digest TYPE VARCHAR(255)
+        USING sui_digest_text(deleted_transaction_digest);
+
+ALTER TABLE datapod_events
+    ALTER COLUMN datapod_id TYPE VARCHAR(255) USING sui_address_text(datapod_id),
+    ALTER COLUMN seller TYPE VARCHAR(255) USING COALESCE(sui_address_text(seller), ''),
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest);
+
+ALTER TABLE datapod_events
+    ALTER COLUMN seller SET NOT NULL;
+
+-- Drop functions
+DROP FUNCTION IF EXISTS sui_digest_text(BYTEA);
+DROP FUNCTION IF EXISTS sui_digest(TEXT);
+DROP FUNCTION IF EXISTS sui_address_text(BYTEA);
+DROP FUNCTION IF EXISTS sui_address(TEXT);
@@ -0,0 +1,165 @@
+-- Store addresses, object IDs and digests as their 32 raw bytes instead of text, which more
+-- than halves the size of these columns and of the indexes over them.
+
+-- Address or object ID from its hex form, with or without the 0x prefix
+CREATE OR REPLACE FUNCTION sui_address(address TEXT)
+RETURNS BYTEA AS $$
+    SELECT decode(lpad(regexp_replace(lower(address), '^0x', ''), 64, '0'), 'hex');
+$$ LANGUAGE SQL IMMUTABLE STRICT;
+
+-- Address or object ID in its 0x-prefixed hex form
+CREATE OR REPLACE FUNCTION sui_address_text(address BYTEA)
+RETURNS TEXT AS $$
+    SELECT '0x' || encode(address, 'hex');
+$$ LANGUAGE SQL IMMUTABLE STRICT;
+
+-- Transaction or object digest from its Base58 form
+CREATE OR REPLACE FUNCTION sui_digest(encoded TEXT)
+RETURNS BYTEA AS $$
+DECLARE
+    alphabet CONSTANT TEXT := '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+    value NUMERIC := 0;
+    digit INT;
+    digest BYTEA := decode(repeat('00', 32), 'hex');
+BEGIN
+    FOR i IN 1 .. length(encoded) LOOP
+        digit := strpos(alphabet, substr(encoded, i, 1)) - 1;
+        IF digit < 0 THEN
+            RAISE EXCEPTION 'Invalid Base58 digest: %', encoded;
+        END IF;
+        value := value * 58 + digit;
+    END LOOP;
+
+    FOR i IN REVERSE 31 .. 0 LOOP
+        digest := set_byte(digest, i, mod(value, 256)::INT);
+        value := div(value, 256);
+    END LOOP;
+
+    IF value > 0 THEN
+        RAISE EXCEPTION 'Digest longer than 32 bytes: %', encoded;
+    END IF;
+    RETURN digest;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE STRICT;
+
+-- Transaction or object digest in its Base58 form
+CREATE OR REPLACE FUNCTION sui_digest_text(digest BYTEA)
+RETURNS TEXT AS $$
+DECLARE
+    alphabet CONSTANT TEXT := '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+    value NUMERIC := 0;
+    encoded TEXT := '';
+    zeros INT := 0;
+BEGIN
+    FOR i IN 0 .. length(digest) - 1 LOOP
+        value := value * 256 + get_byte(digest, i);
+    END LOOP;
+
+    WHILE value > 0 LOOP
+        encoded := substr(alphabet, mod(value, 58)::INT + 1, 1) || encoded;
+        value := div(value, 58);
+    END LOOP;
+
+    -- Each leading zero byte is written as a leading '1'
+    WHILE zeros < length(digest) AND get_byte(digest, zeros) = 0 LOOP
+        encoded := '1' || encoded;
+        zeros := zeros + 1;
+    END LOOP;
+    RETURN encoded;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE STRICT;
+
+-- Price updates carry no seller and used to store an empty string
+ALTER TABLE datapod_events
+    ALTER COLUMN seller DROP NOT NULL;
+
+ALTER TABLE datapod_events
+    ALTER COLUMN datapod_id TYPE BYTEA USING sui_address(datapod_id),
+    ALTER COLUMN seller TYPE BYTEA USING sui_address(NULLIF(seller, '')),
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest);
+
+ALTER TABLE smart_contract_objects
+    ALTER COLUMN object_id TYPE BYTEA USING sui_address(object_id),
+    ALTER COLUMN digest TYPE BYTEA USING sui_digest(digest),
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest),
+    ALTER COLUMN deleted_transaction_digest TYPE BYTEA
+        USING sui_digest(deleted_transaction_digest);
+
+ALTER TABLE object_versions
+    ALTER COLUMN object_id TYPE BYTEA USING sui_address(object_id),
+    ALTER COLUMN digest TYPE BYTEA USING sui_digest(digest),
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest);
+
+ALTER TABLE transaction_digests
+    ALTER COLUMN tx_digest TYPE BYTEA USING sui_digest(tx_digest),
+    ALTER COLUMN sender TYPE BYTEA USING sui_address(sender);
+
+-- The tables as they read with text identifiers, for ad-hoc queries
+CREATE OR REPLACE VIEW datapod_events_text AS
+SELECT
+    id,
+    event_type,
+    sui_address_text(datapod_id) AS datapod_id,
+    sui_address_text(seller) AS seller,
+    title,
+    category,
+    price_sui,
+    kiosk_id,
+    old_price,
+    new_price,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    event_index,
+    timestamp,
+    created_at
+FROM datapod_events;
+
+CREATE OR REPLACE VIEW smart_contract_objects_text AS
+SELECT
+    id,
+    sui_address_text(object_id) AS object_id,
+    object_type,
+    owner,
+    version,
+    sui_digest_text(digest) AS digest,
+    content_type,
+    data,
+    checkpoint_sequence_number,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    created_at,
+    is_deleted,
+    deletion_kind,
+    deleted_at_checkpoint,
+    sui_digest_text(deleted_transaction_digest) AS deleted_transaction_digest
+FROM smart_contract_objects;
+
+CREATE OR REPLACE VIEW object_versions_text AS
+SELECT
+    sui_address_text(object_id) AS object_id,
+    version,
+    sui_digest_text(digest) AS digest,
+    object_type,
+    owner,
+    data,
+    deletion_kind,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    created_at
+FROM object_versions;
+
+CREATE OR REPLACE VIEW transaction_digests_text AS
+SELECT
+    id,
+    sui_digest_text(tx_digest) AS tx_digest,
+    checkpoint_sequence_number,
+    created_at,
+    sui_address_text(sender) AS sender,
+    execution_status,
+    gas_budget,
+    computation_cost,
+    storage_cost,
+    storage_rebate,
+    non_refundable_storage_fee,
+    timestamp,
+    transaction_index
+FROM transaction_digests;
@@ -0,0 +1,93 @@
+-- Only rows in attached partitions are kept; detached partitions are left as standalone tables
+DROP VIEW IF EXISTS datapod_events_text;
+DROP VIEW IF EXISTS object_versions_text;
+DROP VIEW IF EXISTS transaction_digests_text;
+
+-- datapod_events
+ALTER TABLE datapod_events RENAME TO datapod_events_partitioned;
+CREATE TABLE datapod_events (LIKE datapod_events_partitioned INCLUDING DEFAULTS);
+INSERT INTO datapod_events SELECT * FROM datapod_events_partitioned;
+ALTER SEQUENCE datapod_events_id_seq OWNED BY datapod_events.id;
+DROP TABLE datapod_events_partitioned;
+ALTER TABLE datapod_events ADD PRIMARY KEY (id);
+ALTER TABLE datapod_events ADD UNIQUE (transaction_digest, event_index);
+CREATE INDEX IF NOT EXISTS idx_datapod_events_datapod_id ON datapod_events(datapod_id);
+CREATE INDEX IF NOT EXISTS idx_datapod_events_seller ON datapod_events(seller);
+CREATE INDEX IF NOT EXISTS idx_datapod_events_checkpoint ON datapod_events(checkpoint_sequence_number);
+
+-- transaction_digests
+ALTER TABLE transaction_digests RENAME TO transaction_digests_partitioned;
+CREATE TABLE transaction_digests (LIKE transaction_digests_partitioned INCLUDING DEFAULTS);
+INSERT INTO transaction_digests SELECT * FROM transaction_digests_partitioned;
+ALTER SEQUENCE transaction_digests_id_seq OWNED BY transaction_digests.id;
+DROP TABLE transaction_digests_partitioned;
+ALTER TABLE transaction_digests ADD PRIMARY KEY (id);
+ALTER TABLE transaction_digests ADD UNIQUE (tx_digest);
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_checkpoint ON transaction_digests(checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_sender ON transaction_digests(sender);
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_status ON transaction_digests(execution_status);
+
+-- object_versions
+ALTER TABLE object_versions RENAME TO object_versions_partitioned;
+CREATE TABLE object_versions (LIKE object_versions_partitioned INCLUDING DEFAULTS);
+INSERT INTO object_versions SELECT * FROM object_versions_partitioned;
+DROP TABLE object_versions_partitioned;
+ALTER TABLE object_versions ADD PRIMARY KEY (object_id, version);
+CREATE INDEX IF NOT EXISTS idx_object_versions_type ON object_versions(object_type);
+CREATE INDEX IF NOT EXISTS idx_object_versions_checkpoint ON object_versions(checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_object_versions_transaction ON object_versions(transaction_digest);
+
+-- Recreate views
+CREATE OR REPLACE VIEW datapod_events_text AS
+SELECT
+    id,
+    event_type,
+    sui_address_text(datapod_id) AS datapod_id,
+    sui_address_text(seller) AS seller,
+    title,
+    category,
+    price_sui,
+    kiosk_id,
+    old_price,
+    new_price,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    event_index,
+    timestamp,
+    created_at
+FROM datapod_events;
+
+CREATE OR REPLACE VIEW object_versions_text AS
+SELECT
+    sui_address_text(object_id) AS object_id,
+    version,
+    sui_digest_text(digest) AS digest,
+    object_type,
+    owner,
+    data,
+    deletion_kind,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    created_at
+FROM object_versions;
+
+CREATE OR REPLACE VIEW transaction_digests_text AS
+SELECT
+    id,
+    sui_digest_text(tx_digest) AS tx_digest,
+    checkpoint_sequence_number,
+    created_at,
+    sui_address_text(sender) AS sender,
+    execution_status,
+    gas_budget,
+    computation_cost,
+    storage_cost,
+    storage_rebate,
+    non_refundable_storage_fee,
+    timestamp,
+    transaction_index
+FROM transaction_digests;
+
+-- Drop partition management
+DROP FUNCTION IF EXISTS ensure_checkpoint_partitions(TEXT, BIGINT, BIGINT, BIGINT);
+DROP TABLE IF EXISTS checkpoint_partitions;
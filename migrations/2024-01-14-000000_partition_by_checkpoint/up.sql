@@ -0,0 +1,225 @@
+-- Partition the append-only tables by checkpoint_sequence_number range, so old checkpoints can
+-- be detached or dropped as a whole and vacuum and index maintenance only touch recent ones.
+-- smart_contract_objects is upserted by object ID and stays a single table; its history lives
+-- in object_versions.
+
+-- Partitions created by ensure_checkpoint_partitions, attached or not
+CREATE TABLE IF NOT EXISTS checkpoint_partitions (
+    partition_name VARCHAR(255) PRIMARY KEY,
+    parent_table VARCHAR(255) NOT NULL,
+    from_checkpoint BIGINT NOT NULL,
+    to_checkpoint BIGINT NOT NULL,
+    detached BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_checkpoint_partitions_parent
+    ON checkpoint_partitions(parent_table, from_checkpoint);
+
+-- Create partitions of `parent_name`, each covering `checkpoints_per_partition` checkpoints,
+-- until every checkpoint from `first_checkpoint` to `last_checkpoint` has one. New partitions
+-- continue from the last one created, so changing the size never makes ranges overlap.
+CREATE OR REPLACE FUNCTION ensure_checkpoint_partitions(
+    parent_name TEXT,
+    first_checkpoint BIGINT,
+    last_checkpoint BIGINT,
+    checkpoints_per_partition BIGINT
+)
+RETURNS INTEGER AS $$
+DECLARE
+    next_start BIGINT;
+    partition TEXT;
+    created INTEGER := 0;
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('checkpoint_partitions:' || parent_name));
+
+    SELECT MAX(to_checkpoint) INTO next_start
+    FROM checkpoint_partitions
+    WHERE parent_table = parent_name;
+
+    IF next_start IS NULL THEN
+        next_start := first_checkpoint - first_checkpoint % checkpoints_per_partition;
+    END IF;
+
+    WHILE next_start <= last_checkpoint LOOP
+        partition := format('%s_p%s', parent_name, next_start);
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF %I FOR VALUES FROM (%s) TO (%s)',
+            partition, parent_name, next_start, next_start + checkpoints_per_partition
+        );
+        INSERT INTO checkpoint_partitions (partition_name, parent_table, from_checkpoint, to_checkpoint)
+        VALUES (partition, parent_name, next_start, next_start + checkpoints_per_partition);
+
+        next_start := next_start + checkpoints_per_partition;
+        created := created + 1;
+    END LOOP;
+
+    RETURN created;
+END;
+$$ LANGUAGE plpgsql;
+
+-- The text views are recreated over the partitioned tables below
+DROP VIEW IF EXISTS datapod_events_text;
+DROP VIEW IF EXISTS object_versions_text;
+DROP VIEW IF EXISTS transaction_digests_text;
+
+-- datapod_events
+ALTER TABLE datapod_events RENAME TO datapod_events_unpartitioned;
+
+CREATE TABLE datapod_events (
+    id BIGINT NOT NULL DEFAULT nextval('datapod_events_id_seq'),
+    event_type VARCHAR(255) NOT NULL,
+    datapod_id BYTEA NOT NULL,
+    seller BYTEA,
+    title VARCHAR(1024),
+    category VARCHAR(255),
+    price_sui NUMERIC(20, 0),
+    kiosk_id VARCHAR(255),
+    old_price NUMERIC(20, 0),
+    new_price NUMERIC(20, 0),
+    transaction_digest BYTEA NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    event_index BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+) PARTITION BY RANGE (checkpoint_sequence_number);
+
+SELECT ensure_checkpoint_partitions(
+    'datapod_events',
+    COALESCE(MIN(checkpoint_sequence_number), 0),
+    COALESCE(MAX(checkpoint_sequence_number), 0) + 1000000,
+    1000000
+)
+FROM datapod_events_unpartitioned;
+
+INSERT INTO datapod_events SELECT * FROM datapod_events_unpartitioned;
+ALTER SEQUENCE datapod_events_id_seq OWNED BY datapod_events.id;
+DROP TABLE datapod_events_unpartitioned;
+
+-- Unique constraints of a partitioned table must include the partition key
+ALTER TABLE datapod_events ADD PRIMARY KEY (id, checkpoint_sequence_number);
+ALTER TABLE datapod_events
+    ADD UNIQUE (transaction_digest, event_index, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_datapod_events_datapod_id ON datapod_events(datapod_id);
+CREATE INDEX IF NOT EXISTS idx_datapod_events_seller ON datapod_events(seller);
+CREATE INDEX IF NOT EXISTS idx_datapod_events_checkpoint ON datapod_events(checkpoint_sequence_number);
+
+-- transaction_digests
+ALTER TABLE transaction_digests RENAME TO transaction_digests_unpartitioned;
+
+CREATE TABLE transaction_digests (
+    id BIGINT NOT NULL DEFAULT nextval('transaction_digests_id_seq'),
+    tx_digest BYTEA NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    sender BYTEA,
+    execution_status VARCHAR(32),
+    gas_budget NUMERIC(20, 0),
+    computation_cost NUMERIC(20, 0),
+    storage_cost NUMERIC(20, 0),
+    storage_rebate NUMERIC(20, 0),
+    non_refundable_storage_fee NUMERIC(20, 0),
+    timestamp BIGINT,
+    transaction_index BIGINT
+) PARTITION BY RANGE (checkpoint_sequence_number);
+
+SELECT ensure_checkpoint_partitions(
+    'transaction_digests',
+    COALESCE(MIN(checkpoint_sequence_number), 0),
+    COALESCE(MAX(checkpoint_sequence_number), 0) + 1000000,
+    1000000
+)
+FROM transaction_digests_unpartitioned;
+
+INSERT INTO transaction_digests SELECT * FROM transaction_digests_unpartitioned;
+ALTER SEQUENCE transaction_digests_id_seq OWNED BY transaction_digests.id;
+DROP TABLE transaction_digests_unpartitioned;
+
+ALTER TABLE transaction_digests ADD PRIMARY KEY (id, checkpoint_sequence_number);
+ALTER TABLE transaction_digests ADD UNIQUE (tx_digest, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_checkpoint ON transaction_digests(checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_sender ON transaction_digests(sender);
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_status ON transaction_digests(execution_status);
+
+-- object_versions
+ALTER TABLE object_versions RENAME TO object_versions_unpartitioned;
+
+CREATE TABLE object_versions (
+    object_id BYTEA NOT NULL,
+    version NUMERIC(20, 0) NOT NULL,
+    digest BYTEA NOT NULL,
+    object_type VARCHAR(1024) NOT NULL,
+    owner VARCHAR(255),
+    data JSONB,
+    deletion_kind VARCHAR(32),
+    transaction_digest BYTEA NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+) PARTITION BY RANGE (checkpoint_sequence_number);
+
+SELECT ensure_checkpoint_partitions(
+    'object_versions',
+    COALESCE(MIN(checkpoint_sequence_number), 0),
+    COALESCE(MAX(checkpoint_sequence_number), 0) + 1000000,
+    1000000
+)
+FROM object_versions_unpartitioned;
+
+INSERT INTO object_versions SELECT * FROM object_versions_unpartitioned;
+DROP TABLE object_versions_unpartitioned;
+
+ALTER TABLE object_versions ADD PRIMARY KEY (object_id, version, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_object_versions_type ON object_versions(object_type);
+CREATE INDEX IF NOT EXISTS idx_object_versions_checkpoint ON object_versions(checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_object_versions_transaction ON object_versions(transaction_digest);
+
+-- The tables as they read with text identifiers, for ad-hoc queries
+CREATE OR REPLACE VIEW datapod_events_text AS
+SELECT
+    id,
+    event_type,
+    sui_address_text(datapod_id) AS datapod_id,
+    sui_address_text(seller) AS seller,
+    title,
+    category,
+    price_sui,
+    kiosk_id,
+    old_price,
+    new_price,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    event_index,
+    timestamp,
+    created_at
+FROM datapod_events;
+
+CREATE OR REPLACE VIEW object_versions_text AS
+SELECT
+    sui_address_text(object_id) AS object_id,
+    version,
+    sui_digest_text(digest) AS digest,
+    object_type,
+    owner,
+    data,
+    deletion_kind,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    created_at
+FROM object_versions;
+
+CREATE OR REPLACE VIEW transaction_digests_text AS
+SELECT
+    id,
+    sui_digest_text(tx_digest) AS tx_digest,
+    checkpoint_sequence_number,
+    created_at,
+    sui_address_text(sender) AS sender,
+    execution_status,
+    gas_budget,
+    computation_cost,
+    storage_cost,
+    storage_rebate,
+    non_refundable_storage_fee,
+    timestamp,
+    transaction_index
+FROM transaction_digests;
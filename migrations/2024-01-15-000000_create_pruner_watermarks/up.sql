@@ -0,0 +1,7 @@
+-- Create table recording how far the pruner has deleted each table: every row before
+-- `pruned_before_checkpoint` is gone
+CREATE TABLE IF NOT EXISTS pruner_watermarks (
+    table_name VARCHAR(255) PRIMARY KEY,
+    pruned_before_checkpoint BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
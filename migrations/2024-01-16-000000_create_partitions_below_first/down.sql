@@ -0,0 +1,39 @@
+-- Restore ensure_checkpoint_partitions that only creates partitions after the last one
+CREATE OR REPLACE FUNCTION ensure_checkpoint_partitions(
+    parent_name TEXT,
+    first_checkpoint BIGINT,
+    last_checkpoint BIGINT,
+    checkpoints_per_partition BIGINT
+)
+RETURNS INTEGER AS $$
+DECLARE
+    next_start BIGINT;
+    partition TEXT;
+    created INTEGER := 0;
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('checkpoint_partitions:' || parent_name));
+
+    SELECT MAX(to_checkpoint) INTO next_start
+    FROM checkpoint_partitions
+    WHERE parent_table = parent_name;
+
+    IF next_start IS NULL THEN
+        next_start := first_checkpoint - first_checkpoint % checkpoints_per_partition;
+    END IF;
+
+    WHILE next_start <= last_checkpoint LOOP
+        partition := format('%s_p%s', parent_name, next_start);
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF %I FOR VALUES FROM (%s) TO (%s)',
+            partition, parent_name, next_start, next_start + checkpoints_per_partition
+        );
+        INSERT INTO checkpoint_partitions (partition_name, parent_table, from_checkpoint, to_checkpoint)
+        VALUES (partition, parent_name, next_start, next_start + checkpoints_per_partition);
+
+        next_start := next_start + checkpoints_per_partition;
+        created := created + 1;
+    END LOOP;
+
+    RETURN created;
+END;
+$$ LANGUAGE plpgsql;
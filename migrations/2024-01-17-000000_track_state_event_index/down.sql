@@ -0,0 +1,2 @@
+ALTER TABLE escrows DROP COLUMN IF EXISTS last_event_index;
+ALTER TABLE purchases DROP COLUMN IF EXISTS last_event_index;
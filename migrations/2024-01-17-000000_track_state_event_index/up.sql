@@ -0,0 +1,16 @@
+-- Track the index of the last event applied to each purchase and escrow, so that replaying
+-- older checkpoints never moves their state backwards
+ALTER TABLE purchases ADD COLUMN IF NOT EXISTS last_event_index BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE escrows ADD COLUMN IF NOT EXISTS last_event_index BIGINT NOT NULL DEFAULT 0;
+
+UPDATE purchases p SET last_event_index = COALESCE((
+    SELECT MAX(e.event_index) FROM purchase_events e
+    WHERE e.purchase_id = p.purchase_id
+      AND e.checkpoint_sequence_number = p.last_updated_checkpoint
+), 0);
+
+UPDATE escrows s SET last_event_index = COALESCE((
+    SELECT MAX(e.event_index) FROM escrow_events e
+    WHERE e.escrow_id = s.escrow_id
+      AND e.checkpoint_sequence_number = s.last_updated_checkpoint
+), 0);
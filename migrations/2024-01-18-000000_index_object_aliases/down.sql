@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_smart_contract_objects_purchase_alias;
+DROP INDEX IF EXISTS idx_smart_contract_objects_datapod_alias;
@@ -0,0 +1,6 @@
+-- Index the application-level IDs that purchases and escrows refer to listings and purchases
+-- by, so that GraphQL resolves those references without scanning every object
+CREATE INDEX IF NOT EXISTS idx_smart_contract_objects_datapod_alias
+    ON smart_contract_objects ((data ->> 'datapod_id'));
+CREATE INDEX IF NOT EXISTS idx_smart_contract_objects_purchase_alias
+    ON smart_contract_objects ((data ->> 'purchase_id'));
@@ -0,0 +1,3 @@
+ALTER TABLE outbox_consumer_offsets DROP COLUMN IF EXISTS acked_txid;
+DROP INDEX IF EXISTS idx_outbox_commit_order;
+ALTER TABLE outbox DROP COLUMN IF EXISTS txid;
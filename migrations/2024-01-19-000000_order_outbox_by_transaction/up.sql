@@ -0,0 +1,9 @@
+-- Record the ID of the transaction that appended each outbox entry. Consumers read entries in
+-- `(txid, sequence)` order and only from transactions older than any still running, so commits
+-- no longer have to be serialized to keep the order. Existing entries get 0 and so come first.
+ALTER TABLE outbox ADD COLUMN IF NOT EXISTS txid BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE outbox ALTER COLUMN txid SET DEFAULT pg_current_xact_id()::text::bigint;
+CREATE INDEX IF NOT EXISTS idx_outbox_commit_order ON outbox(txid, sequence);
+
+-- Offsets become the `(txid, sequence)` of the last acknowledged entry
+ALTER TABLE outbox_consumer_offsets ADD COLUMN IF NOT EXISTS acked_txid BIGINT NOT NULL DEFAULT 0;
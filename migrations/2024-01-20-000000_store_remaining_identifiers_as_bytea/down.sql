@@ -0,0 +1,51 @@
+-- Drop views
+DROP VIEW IF EXISTS failed_transactions_text;
+DROP VIEW IF EXISTS move_calls_text;
+DROP VIEW IF EXISTS escrow_events_text;
+DROP VIEW IF EXISTS escrows_text;
+DROP VIEW IF EXISTS purchase_events_text;
+DROP VIEW IF EXISTS purchases_text;
+DROP VIEW IF EXISTS datapods_text;
+
+-- Restore text identifiers
+ALTER TABLE outbox
+    ALTER COLUMN entity_id TYPE VARCHAR(255) USING sui_address_text(entity_id),
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest);
+
+ALTER TABLE webhook_deliveries
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest);
+
+ALTER TABLE failed_transactions
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest),
+    ALTER COLUMN sender TYPE VARCHAR(255) USING sui_address_text(sender);
+
+ALTER TABLE move_calls
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest),
+    ALTER COLUMN package TYPE VARCHAR(255) USING sui_address_text(package),
+    ALTER COLUMN sender TYPE VARCHAR(255) USING sui_address_text(sender);
+
+ALTER TABLE escrow_events
+    ALTER COLUMN escrow_id TYPE VARCHAR(255) USING sui_address_text(escrow_id),
+    ALTER COLUMN buyer TYPE VARCHAR(255) USING sui_address_text(buyer),
+    ALTER COLUMN seller TYPE VARCHAR(255) USING sui_address_text(seller),
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest);
+
+ALTER TABLE escrows
+    ALTER COLUMN escrow_id TYPE VARCHAR(255) USING sui_address_text(escrow_id),
+    ALTER COLUMN buyer TYPE VARCHAR(255) USING sui_address_text(buyer),
+    ALTER COLUMN seller TYPE VARCHAR(255) USING sui_address_text(seller);
+
+ALTER TABLE purchase_events
+    ALTER COLUMN purchase_id TYPE VARCHAR(255) USING sui_address_text(purchase_id),
+    ALTER COLUMN buyer TYPE VARCHAR(255) USING sui_address_text(buyer),
+    ALTER COLUMN seller TYPE VARCHAR(255) USING sui_address_text(seller),
+    ALTER COLUMN transaction_digest TYPE VARCHAR(255) USING sui_digest_text(transaction_digest);
+
+ALTER TABLE purchases
+    ALTER COLUMN purchase_id TYPE VARCHAR(255) USING sui_address_text(purchase_id),
+    ALTER COLUMN buyer TYPE VARCHAR(255) USING sui_address_text(buyer),
+    ALTER COLUMN seller TYPE VARCHAR(255) USING sui_address_text(seller);
+
+ALTER TABLE datapods
+    ALTER COLUMN datapod_id TYPE VARCHAR(255) USING sui_address_text(datapod_id),
+    ALTER COLUMN seller TYPE VARCHAR(255) USING sui_address_text(seller);
@@ -0,0 +1,161 @@
+-- Store the addresses, object IDs and digests of the remaining tables as their 32 raw bytes,
+-- using the conversion functions of 2024-01-13. `purchases.datapod_id`,
+-- `purchase_events.datapod_id`, `escrows.purchase_id` and `escrow_events.purchase_id` stay text:
+-- the contract passes them as strings, which need not be object IDs.
+
+ALTER TABLE datapods
+    ALTER COLUMN datapod_id TYPE BYTEA USING sui_address(datapod_id),
+    ALTER COLUMN seller TYPE BYTEA USING sui_address(seller);
+
+ALTER TABLE purchases
+    ALTER COLUMN purchase_id TYPE BYTEA USING sui_address(purchase_id),
+    ALTER COLUMN buyer TYPE BYTEA USING sui_address(buyer),
+    ALTER COLUMN seller TYPE BYTEA USING sui_address(seller);
+
+ALTER TABLE purchase_events
+    ALTER COLUMN purchase_id TYPE BYTEA USING sui_address(purchase_id),
+    ALTER COLUMN buyer TYPE BYTEA USING sui_address(buyer),
+    ALTER COLUMN seller TYPE BYTEA USING sui_address(seller),
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest);
+
+ALTER TABLE escrows
+    ALTER COLUMN escrow_id TYPE BYTEA USING sui_address(escrow_id),
+    ALTER COLUMN buyer TYPE BYTEA USING sui_address(buyer),
+    ALTER COLUMN seller TYPE BYTEA USING sui_address(seller);
+
+ALTER TABLE escrow_events
+    ALTER COLUMN escrow_id TYPE BYTEA USING sui_address(escrow_id),
+    ALTER COLUMN buyer TYPE BYTEA USING sui_address(buyer),
+    ALTER COLUMN seller TYPE BYTEA USING sui_address(seller),
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest);
+
+ALTER TABLE move_calls
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest),
+    ALTER COLUMN package TYPE BYTEA USING sui_address(package),
+    ALTER COLUMN sender TYPE BYTEA USING sui_address(sender);
+
+ALTER TABLE failed_transactions
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest),
+    ALTER COLUMN sender TYPE BYTEA USING sui_address(sender);
+
+ALTER TABLE webhook_deliveries
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest);
+
+ALTER TABLE outbox
+    ALTER COLUMN entity_id TYPE BYTEA USING sui_address(entity_id),
+    ALTER COLUMN transaction_digest TYPE BYTEA USING sui_digest(transaction_digest);
+
+-- The tables as they read with text identifiers, for ad-hoc queries
+CREATE OR REPLACE VIEW datapods_text AS
+SELECT
+    sui_address_text(datapod_id) AS datapod_id,
+    sui_address_text(seller) AS seller,
+    title,
+    category,
+    price_sui,
+    kiosk_id,
+    status,
+    created_checkpoint,
+    published_checkpoint,
+    last_updated_checkpoint,
+    last_event_index,
+    last_updated_timestamp
+FROM datapods;
+
+CREATE OR REPLACE VIEW purchases_text AS
+SELECT
+    sui_address_text(purchase_id) AS purchase_id,
+    datapod_id,
+    sui_address_text(buyer) AS buyer,
+    sui_address_text(seller) AS seller,
+    price_sui,
+    status,
+    created_checkpoint,
+    created_timestamp,
+    completed_timestamp,
+    refunded_timestamp,
+    disputed_timestamp,
+    last_updated_checkpoint,
+    last_event_index,
+    last_updated_timestamp
+FROM purchases;
+
+CREATE OR REPLACE VIEW purchase_events_text AS
+SELECT
+    id,
+    event_type,
+    sui_address_text(purchase_id) AS purchase_id,
+    datapod_id,
+    sui_address_text(buyer) AS buyer,
+    sui_address_text(seller) AS seller,
+    price_sui,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    event_index,
+    timestamp,
+    created_at
+FROM purchase_events;
+
+CREATE OR REPLACE VIEW escrows_text AS
+SELECT
+    sui_address_text(escrow_id) AS escrow_id,
+    purchase_id,
+    sui_address_text(buyer) AS buyer,
+    sui_address_text(seller) AS seller,
+    amount,
+    status,
+    created_checkpoint,
+    created_timestamp,
+    released_checkpoint,
+    released_timestamp,
+    refunded_checkpoint,
+    refunded_timestamp,
+    last_updated_checkpoint,
+    last_event_index,
+    last_updated_timestamp
+FROM escrows;
+
+CREATE OR REPLACE VIEW escrow_events_text AS
+SELECT
+    id,
+    event_type,
+    sui_address_text(escrow_id) AS escrow_id,
+    purchase_id,
+    sui_address_text(buyer) AS buyer,
+    sui_address_text(seller) AS seller,
+    amount,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    event_index,
+    timestamp,
+    created_at
+FROM escrow_events;
+
+CREATE OR REPLACE VIEW move_calls_text AS
+SELECT
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    command_index,
+    sui_address_text(package) AS package,
+    module,
+    function,
+    type_arguments,
+    sui_address_text(sender) AS sender,
+    checkpoint_sequence_number,
+    timestamp,
+    created_at
+FROM move_calls;
+
+CREATE OR REPLACE VIEW failed_transactions_text AS
+SELECT
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    sui_address_text(sender) AS sender,
+    error,
+    failed_command_index,
+    abort_module,
+    abort_function,
+    abort_code,
+    abort_error_name,
+    checkpoint_sequence_number,
+    timestamp,
+    created_at
+FROM failed_transactions;
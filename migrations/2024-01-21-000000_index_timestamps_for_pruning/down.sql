@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_failed_transactions_timestamp;
+DROP INDEX IF EXISTS idx_move_calls_timestamp;
+DROP INDEX IF EXISTS idx_escrow_events_timestamp;
+DROP INDEX IF EXISTS idx_purchase_events_timestamp;
+DROP INDEX IF EXISTS idx_datapod_events_timestamp;
+DROP INDEX IF EXISTS idx_transaction_digests_timestamp;
@@ -0,0 +1,14 @@
+-- Let the pruner find the newest row past an age-based retention from the end of an index,
+-- instead of scanning the table
+CREATE INDEX IF NOT EXISTS idx_transaction_digests_timestamp
+    ON transaction_digests(timestamp, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_datapod_events_timestamp
+    ON datapod_events(timestamp, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_purchase_events_timestamp
+    ON purchase_events(timestamp, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_escrow_events_timestamp
+    ON escrow_events(timestamp, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_move_calls_timestamp
+    ON move_calls(timestamp, checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS idx_failed_transactions_timestamp
+    ON failed_transactions(timestamp, checkpoint_sequence_number);
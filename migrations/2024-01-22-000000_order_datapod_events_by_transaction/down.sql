@@ -0,0 +1,22 @@
+DROP VIEW IF EXISTS datapod_events_text;
+CREATE VIEW datapod_events_text AS
+SELECT
+    id,
+    event_type,
+    sui_address_text(datapod_id) AS datapod_id,
+    sui_address_text(seller) AS seller,
+    title,
+    category,
+    price_sui,
+    kiosk_id,
+    old_price,
+    new_price,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    event_index,
+    timestamp,
+    created_at
+FROM datapod_events;
+
+DROP INDEX IF EXISTS idx_datapod_events_order;
+ALTER TABLE datapod_events DROP COLUMN IF EXISTS transaction_index;
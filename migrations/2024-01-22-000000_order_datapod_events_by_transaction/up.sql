@@ -0,0 +1,47 @@
+-- `event_index` counts the events of one transaction, so events of different transactions in
+-- the same checkpoint are ordered by the transaction's position in the checkpoint first
+ALTER TABLE datapod_events ADD COLUMN IF NOT EXISTS transaction_index BIGINT;
+
+-- Existing rows were inserted in checkpoint order, so numbering the transactions of each
+-- checkpoint by their first row orders them the same way
+UPDATE datapod_events e
+SET transaction_index = t.transaction_index
+FROM (
+    SELECT
+        checkpoint_sequence_number,
+        transaction_digest,
+        ROW_NUMBER() OVER (
+            PARTITION BY checkpoint_sequence_number ORDER BY MIN(id)
+        ) - 1 AS transaction_index
+    FROM datapod_events
+    GROUP BY checkpoint_sequence_number, transaction_digest
+) t
+WHERE e.checkpoint_sequence_number = t.checkpoint_sequence_number
+  AND e.transaction_digest = t.transaction_digest
+  AND e.transaction_index IS NULL;
+
+ALTER TABLE datapod_events ALTER COLUMN transaction_index SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_datapod_events_order
+    ON datapod_events(checkpoint_sequence_number, transaction_index, event_index);
+
+DROP VIEW IF EXISTS datapod_events_text;
+CREATE VIEW datapod_events_text AS
+SELECT
+    id,
+    event_type,
+    sui_address_text(datapod_id) AS datapod_id,
+    sui_address_text(seller) AS seller,
+    title,
+    category,
+    price_sui,
+    kiosk_id,
+    old_price,
+    new_price,
+    sui_digest_text(transaction_digest) AS transaction_digest,
+    checkpoint_sequence_number,
+    transaction_index,
+    event_index,
+    timestamp,
+    created_at
+FROM datapod_events;
@@ -0,0 +1,68 @@
+-- Partitions are contiguous, so those of a table cover a batch if they span from below its
+-- first checkpoint to past its last one. Most batches are covered, and return before taking the
+-- lock, which is held until the batch commits and would otherwise make every commit to the
+-- table wait for the one before it.
+CREATE OR REPLACE FUNCTION ensure_checkpoint_partitions(
+    parent_name TEXT,
+    first_checkpoint BIGINT,
+    last_checkpoint BIGINT,
+    checkpoints_per_partition BIGINT
+)
+RETURNS INTEGER AS $$
+DECLARE
+    lowest BIGINT;
+    next_start BIGINT;
+    partition_start BIGINT;
+    partition TEXT;
+    created INTEGER := 0;
+BEGIN
+    SELECT MIN(from_checkpoint), MAX(to_checkpoint) INTO lowest, next_start
+    FROM checkpoint_partitions
+    WHERE parent_table = parent_name;
+
+    IF lowest <= first_checkpoint AND next_start > last_checkpoint THEN
+        RETURN 0;
+    END IF;
+
+    PERFORM pg_advisory_xact_lock(hashtext('checkpoint_partitions:' || parent_name));
+
+    -- Another transaction may have created partitions while this one waited for the lock
+    SELECT MIN(from_checkpoint), MAX(to_checkpoint) INTO lowest, next_start
+    FROM checkpoint_partitions
+    WHERE parent_table = parent_name;
+
+    IF next_start IS NULL THEN
+        next_start := first_checkpoint - first_checkpoint % checkpoints_per_partition;
+        lowest := next_start;
+    END IF;
+
+    WHILE lowest > first_checkpoint LOOP
+        partition_start := GREATEST(lowest - checkpoints_per_partition, 0);
+        partition := format('%s_p%s', parent_name, partition_start);
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF %I FOR VALUES FROM (%s) TO (%s)',
+            partition, parent_name, partition_start, lowest
+        );
+        INSERT INTO checkpoint_partitions (partition_name, parent_table, from_checkpoint, to_checkpoint)
+        VALUES (partition, parent_name, partition_start, lowest);
+
+        lowest := partition_start;
+        created := created + 1;
+    END LOOP;
+
+    WHILE next_start <= last_checkpoint LOOP
+        partition := format('%s_p%s', parent_name, next_start);
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF %I FOR VALUES FROM (%s) TO (%s)',
+            partition, parent_name, next_start, next_start + checkpoints_per_partition
+        );
+        INSERT INTO checkpoint_partitions (partition_name, parent_table, from_checkpoint, to_checkpoint)
+        VALUES (partition, parent_name, next_start, next_start + checkpoints_per_partition);
+
+        next_start := next_start + checkpoints_per_partition;
+        created := created + 1;
+    END LOOP;
+
+    RETURN created;
+END;
+$$ LANGUAGE plpgsql;
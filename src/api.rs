@@ -0,0 +1,720 @@
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_graphql::{InputObject, SimpleObject};
+use bigdecimal::BigDecimal;
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
+    Json, Router,
+};
+use diesel::dsl::sql;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool, Text};
+use futures::{stream, Stream};
+use diesel_async::{
+    pooled_connection::{
+        bb8::{Pool, PooledConnection},
+        AsyncDieselConnectionManager,
+    },
+    AsyncPgConnection, RunQueryDsl,
+};
+use serde::{Deserialize, Serialize};
+use sui_indexer_alt_framework::pipeline::Processor;
+use tracing::error;
+use url::Url;
+
+use crate::cli::ServeArgs;
+use crate::graphql;
+use crate::handlers::DataPodEventHandler;
+use crate::models::{Address, Digest};
+use crate::schema::{
+    datapod_events, datapods, escrows, pruner_watermarks, purchases, smart_contract_objects,
+    transaction_digests,
+};
+
+/// Shared state of the HTTP API
+#[derive(Clone)]
+pub struct ApiState {
+    pool: Pool<AsyncPgConnection>,
+    default_page_size: i64,
+    max_page_size: i64,
+    stream_poll_interval: Duration,
+}
+
+/// Serve the read-only HTTP and GraphQL APIs over the indexed tables until the process is stopped
+pub async fn serve(database_url: Url, args: ServeArgs) -> Result<()> {
+    let manager = AsyncDieselConnectionManager::<AsyncPgConnection>::new(database_url.as_str());
+    let pool = Pool::builder()
+        .build(manager)
+        .await
+        .context("Failed to create database connection pool")?;
+
+    let state = ApiState {
+        pool,
+        default_page_size: args.default_page_size,
+        max_page_size: args.max_page_size,
+        stream_poll_interval: Duration::from_millis(args.stream_poll_interval_ms),
+    };
+
+    let listener = tokio::net::TcpListener::bind(args.listen_address)
+        .await
+        .with_context(|| format!("Failed to bind {}", args.listen_address))?;
+
+    let schema =
+        graphql::schema(state.clone(), args.graphql_max_depth, args.graphql_max_complexity);
+    let app = router(state).merge(graphql::router(schema));
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/datapods", get(list_datapods))
+        .route("/datapods/{id}", get(get_datapod))
+        .route("/datapods/{id}/events", get(list_datapod_events))
+        .route("/events/stream", get(stream_datapod_events))
+        .route("/sellers/{address}/datapods", get(list_seller_datapods))
+        .route("/transactions/{digest}", get(get_transaction))
+        .route("/objects/{id}", get(get_object))
+        .route("/pruned-tables", get(list_pruned_tables))
+        .with_state(state)
+}
+
+/// Errors returned to API clients
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    NotFound,
+    /// Not found in a table whose rows before this checkpoint were pruned
+    Pruned(i64),
+    Internal(anyhow::Error),
+}
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        Self::Internal(err.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            Self::NotFound => (StatusCode::NOT_FOUND, "Not found".to_string()),
+            Self::Pruned(checkpoint) => {
+                let body = serde_json::json!({
+                    "error": format!("Not found; data before checkpoint {checkpoint} is not available"),
+                    "pruned_before_checkpoint": checkpoint,
+                });
+                return (StatusCode::NOT_FOUND, Json(body)).into_response();
+            }
+            Self::Internal(err) => {
+                // The error chain can name tables, queries and connection details, so it is only
+                // logged
+                error!("Request failed: {err:#}");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            }
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// A page of results, with the cursor to pass as `after` to fetch the next page
+#[derive(Serialize, Debug)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from up to `limit + 1` rows; the extra row only signals that more exist
+    pub fn new(mut rows: Vec<T>, limit: i64, cursor: impl Fn(&T) -> String) -> Self {
+        let has_next = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+        let next_cursor = if has_next { rows.last().map(cursor) } else { None };
+        Self { data: rows, next_cursor }
+    }
+}
+
+/// Pagination query parameters shared by all list endpoints
+#[derive(Deserialize, Debug)]
+pub struct PageParams {
+    pub after: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl ApiState {
+    /// Page size for a request asking for `requested` rows
+    pub fn limit(&self, requested: Option<i64>) -> i64 {
+        requested
+            .unwrap_or(self.default_page_size)
+            .clamp(1, self.max_page_size)
+    }
+
+    pub async fn connection(&self) -> Result<PooledConnection<'_, AsyncPgConnection>> {
+        self.pool
+            .get()
+            .await
+            .context("Failed to get a database connection")
+    }
+}
+
+/// Position of an event in chain order: its checkpoint, the index of its transaction in the
+/// checkpoint, and its index among the events of that transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCursor {
+    pub checkpoint: i64,
+    pub transaction_index: i64,
+    pub event_index: i64,
+}
+
+impl EventCursor {
+    pub fn encode(&self) -> String {
+        format!("{}:{}:{}", self.checkpoint, self.transaction_index, self.event_index)
+    }
+
+    /// A cursor just before the first event of `checkpoint`
+    pub fn before_checkpoint(checkpoint: i64) -> Self {
+        Self {
+            checkpoint: checkpoint - 1,
+            transaction_index: i64::MAX,
+            event_index: i64::MAX,
+        }
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, ApiError> {
+        let invalid = || ApiError::BadRequest(format!("Invalid cursor: {cursor:?}"));
+        let mut parts = cursor.split(':').map(|part| part.parse::<i64>());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(checkpoint)), Some(Ok(transaction_index)), Some(Ok(event_index)), None) => {
+                Ok(Self { checkpoint, transaction_index, event_index })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The ID a page of listings ends at, which they are ordered by
+fn id_cursor(cursor: &str) -> Result<Address, ApiError> {
+    cursor
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("Invalid cursor: {cursor:?}")))
+}
+
+/// A row of `datapods`
+#[derive(Queryable, Selectable, Serialize, SimpleObject, Debug, Clone)]
+#[diesel(table_name = datapods)]
+#[graphql(complex, name = "DataPod")]
+pub struct DataPodView {
+    pub datapod_id: Address,
+    pub seller: Option<Address>,
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub price_sui: Option<BigDecimal>,
+    pub kiosk_id: Option<String>,
+    pub status: Option<String>,
+    pub created_checkpoint: Option<i64>,
+    pub published_checkpoint: Option<i64>,
+    pub last_updated_checkpoint: i64,
+    pub last_event_index: i64,
+    pub last_updated_timestamp: i64,
+}
+
+/// A row of `datapod_events`
+#[derive(Queryable, Selectable, Serialize, SimpleObject, Debug)]
+#[diesel(table_name = datapod_events)]
+#[graphql(complex, name = "Event")]
+pub struct DataPodEventView {
+    pub event_type: String,
+    pub datapod_id: Address,
+    pub seller: Option<Address>,
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub price_sui: Option<BigDecimal>,
+    pub kiosk_id: Option<String>,
+    pub old_price: Option<BigDecimal>,
+    pub new_price: Option<BigDecimal>,
+    pub transaction_digest: Digest,
+    pub checkpoint_sequence_number: i64,
+    pub transaction_index: i64,
+    pub event_index: i64,
+    pub timestamp: i64,
+}
+
+impl DataPodEventView {
+    pub fn cursor(&self) -> EventCursor {
+        EventCursor {
+            checkpoint: self.checkpoint_sequence_number,
+            transaction_index: self.transaction_index,
+            event_index: self.event_index,
+        }
+    }
+}
+
+/// A row of `transaction_digests`
+#[derive(Queryable, Selectable, Serialize, SimpleObject, Debug, Clone)]
+#[diesel(table_name = transaction_digests)]
+#[graphql(name = "Transaction")]
+pub struct TransactionView {
+    pub tx_digest: Digest,
+    pub checkpoint_sequence_number: i64,
+    pub sender: Option<Address>,
+    pub execution_status: Option<String>,
+    pub gas_budget: Option<BigDecimal>,
+    pub computation_cost: Option<BigDecimal>,
+    pub storage_cost: Option<BigDecimal>,
+    pub storage_rebate: Option<BigDecimal>,
+    pub non_refundable_storage_fee: Option<BigDecimal>,
+    pub timestamp: Option<i64>,
+    pub transaction_index: Option<i64>,
+}
+
+/// A row of `pruner_watermarks`: every row of the table before the checkpoint was pruned
+#[derive(Queryable, Selectable, Serialize, SimpleObject, Debug)]
+#[diesel(table_name = pruner_watermarks)]
+#[graphql(name = "PrunedTable")]
+pub struct PrunedTableView {
+    pub table_name: String,
+    pub pruned_before_checkpoint: i64,
+}
+
+/// A row of `smart_contract_objects`
+#[derive(Queryable, Selectable, Serialize, Debug)]
+#[diesel(table_name = smart_contract_objects)]
+pub struct ObjectView {
+    pub object_id: Address,
+    pub object_type: String,
+    pub owner: Option<String>,
+    pub version: BigDecimal,
+    pub digest: Digest,
+    pub content_type: Option<String>,
+    pub data: Option<serde_json::Value>,
+    pub checkpoint_sequence_number: i64,
+    pub transaction_digest: Digest,
+    pub is_deleted: bool,
+    pub deletion_kind: Option<String>,
+    pub deleted_at_checkpoint: Option<i64>,
+    pub deleted_transaction_digest: Option<Digest>,
+}
+
+/// A row of `purchases`
+#[derive(Queryable, Selectable, Serialize, SimpleObject, Debug, Clone)]
+#[diesel(table_name = purchases)]
+#[graphql(complex, name = "Purchase")]
+pub struct PurchaseView {
+    pub purchase_id: Address,
+    pub datapod_id: Option<String>,
+    pub buyer: Address,
+    pub seller: Option<Address>,
+    pub price_sui: Option<BigDecimal>,
+    pub status: String,
+    pub created_checkpoint: Option<i64>,
+    pub created_timestamp: Option<i64>,
+    pub completed_timestamp: Option<i64>,
+    pub refunded_timestamp: Option<i64>,
+    pub disputed_timestamp: Option<i64>,
+    pub last_updated_checkpoint: i64,
+    pub last_updated_timestamp: i64,
+}
+
+/// A row of `escrows`
+#[derive(Queryable, Selectable, Serialize, SimpleObject, Debug, Clone)]
+#[diesel(table_name = escrows)]
+#[graphql(complex, name = "Escrow")]
+pub struct EscrowView {
+    pub escrow_id: Address,
+    pub purchase_id: Option<String>,
+    pub buyer: Option<Address>,
+    pub seller: Option<Address>,
+    pub amount: BigDecimal,
+    pub status: String,
+    pub created_checkpoint: Option<i64>,
+    pub created_timestamp: Option<i64>,
+    pub released_checkpoint: Option<i64>,
+    pub released_timestamp: Option<i64>,
+    pub refunded_checkpoint: Option<i64>,
+    pub refunded_timestamp: Option<i64>,
+    pub last_updated_checkpoint: i64,
+    pub last_updated_timestamp: i64,
+}
+
+async_graphql::scalar!(Address, "Address", "A Sui address or object ID in `0x`-prefixed hex");
+async_graphql::scalar!(Digest, "Digest", "A transaction or object digest in Base58");
+
+/// Filters accepted by the DataPod event endpoints
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct EventFilter {
+    pub event_type: Option<String>,
+    pub seller: Option<Address>,
+    pub datapod_id: Option<Address>,
+}
+
+/// Filters accepted by the DataPod list endpoints
+#[derive(Deserialize, InputObject, Debug, Default, Clone)]
+pub struct DataPodFilter {
+    pub status: Option<String>,
+    pub category: Option<String>,
+    pub seller: Option<Address>,
+    /// Lowest price in MIST, inclusive
+    pub min_price: Option<u64>,
+    /// Highest price in MIST, inclusive
+    pub max_price: Option<u64>,
+}
+
+async fn list_datapods(
+    State(state): State<ApiState>,
+    Query(filter): Query<DataPodFilter>,
+    Query(params): Query<PageParams>,
+) -> ApiResult<Page<DataPodView>> {
+    let limit = state.limit(params.limit);
+    let after = params.after.as_deref().map(id_cursor).transpose()?;
+    let mut conn = state.connection().await?;
+    let rows = load_datapods(&mut conn, &filter, after, limit).await?;
+    Ok(Json(Page::new(rows, limit, |row| row.datapod_id.to_string())))
+}
+
+async fn list_seller_datapods(
+    State(state): State<ApiState>,
+    Path(address): Path<Address>,
+    Query(filter): Query<DataPodFilter>,
+    Query(params): Query<PageParams>,
+) -> ApiResult<Page<DataPodView>> {
+    let filter = DataPodFilter { seller: Some(address), ..filter };
+    let limit = state.limit(params.limit);
+    let after = params.after.as_deref().map(id_cursor).transpose()?;
+    let mut conn = state.connection().await?;
+    let rows = load_datapods(&mut conn, &filter, after, limit).await?;
+    Ok(Json(Page::new(rows, limit, |row| row.datapod_id.to_string())))
+}
+
+/// Up to `limit + 1` DataPod listings after `after`, ordered by ID, which is stable while
+/// listings change
+pub async fn load_datapods(
+    conn: &mut AsyncPgConnection,
+    filter: &DataPodFilter,
+    after: Option<Address>,
+    limit: i64,
+) -> Result<Vec<DataPodView>> {
+    let mut query = datapods::table
+        .select(DataPodView::as_select())
+        .order(datapods::datapod_id)
+        .limit(limit + 1)
+        .into_boxed();
+
+    if let Some(after) = after {
+        query = query.filter(datapods::datapod_id.gt(after));
+    }
+    if let Some(status) = &filter.status {
+        query = query.filter(datapods::status.eq(status.clone()));
+    }
+    if let Some(category) = &filter.category {
+        query = query.filter(datapods::category.eq(category.clone()));
+    }
+    if let Some(seller) = filter.seller {
+        query = query.filter(datapods::seller.eq(seller));
+    }
+    if let Some(min_price) = filter.min_price {
+        query = query.filter(datapods::price_sui.ge(BigDecimal::from(min_price)));
+    }
+    if let Some(max_price) = filter.max_price {
+        query = query.filter(datapods::price_sui.le(BigDecimal::from(max_price)));
+    }
+
+    Ok(query.load(conn).await?)
+}
+
+async fn get_datapod(
+    State(state): State<ApiState>,
+    Path(id): Path<Address>,
+) -> ApiResult<DataPodView> {
+    let mut conn = state.connection().await?;
+    datapods::table
+        .find(id)
+        .select(DataPodView::as_select())
+        .first(&mut conn)
+        .await
+        .optional()?
+        .map(Json)
+        .ok_or(ApiError::NotFound)
+}
+
+async fn list_datapod_events(
+    State(state): State<ApiState>,
+    Path(id): Path<Address>,
+    Query(params): Query<PageParams>,
+) -> ApiResult<Page<DataPodEventView>> {
+    let limit = state.limit(params.limit);
+    let after = params.after.as_deref().map(EventCursor::decode).transpose()?;
+    let filter = EventFilter {
+        datapod_id: Some(id),
+        ..Default::default()
+    };
+    let mut conn = state.connection().await?;
+    let rows = load_datapod_events(&mut conn, &filter, after, limit).await?;
+    Ok(Json(Page::new(rows, limit, |row| row.cursor().encode())))
+}
+
+/// Where a stream starts: after an event cursor, or at the first event of a checkpoint
+#[derive(Deserialize, Debug)]
+pub struct StreamParams {
+    pub after: Option<String>,
+    pub from_checkpoint: Option<i64>,
+}
+
+/// Stream DataPod events as Server-Sent Events as they are committed. Each event carries its
+/// cursor as the SSE `id`, so a reconnecting client resumes after the last event it received
+/// through `Last-Event-ID`. Without a starting point, only events committed from now on are sent.
+async fn stream_datapod_events(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(filter): Query<EventFilter>,
+    Query(params): Query<StreamParams>,
+) -> Result<Sse<impl Stream<Item = Result<Event>>>, ApiError> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+
+    let start = match (last_event_id.or(params.after.as_deref()), params.from_checkpoint) {
+        (Some(after), _) => Some(EventCursor::decode(after)?),
+        (None, Some(checkpoint)) => Some(EventCursor::before_checkpoint(checkpoint)),
+        (None, None) => {
+            let mut conn = state.connection().await?;
+            latest_event_cursor(&mut conn).await?
+        }
+    };
+
+    let stream = stream::try_unfold(
+        (start, VecDeque::new()),
+        move |(mut cursor, mut pending): (Option<EventCursor>, VecDeque<DataPodEventView>)| {
+            let state = state.clone();
+            let filter = filter.clone();
+            async move {
+                loop {
+                    if let Some(row) = pending.pop_front() {
+                        let event = Event::default()
+                            .id(row.cursor().encode())
+                            .event(row.event_type.as_str())
+                            .json_data(&row)?;
+                        return Ok(Some((event, (cursor, pending))));
+                    }
+
+                    let rows = {
+                        let mut conn = state.connection().await?;
+                        let limit = state.max_page_size;
+                        load_datapod_events(&mut conn, &filter, cursor, limit).await?
+                    };
+
+                    match rows.last() {
+                        Some(last) => {
+                            cursor = Some(last.cursor());
+                            pending.extend(rows);
+                        }
+                        None => tokio::time::sleep(state.stream_poll_interval).await,
+                    }
+                }
+            }
+        },
+    );
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Events of the checkpoints the DataPod events pipeline has committed in full. A concurrent
+/// pipeline commits checkpoints out of order, so rows of a lower checkpoint can land after those
+/// above its watermark; a cursor that moved past them would skip them for good.
+fn committed_events() -> Box<dyn BoxableExpression<datapod_events::table, Pg, SqlType = Bool>> {
+    Box::new(
+        sql::<Bool>(
+            "checkpoint_sequence_number <= \
+             (SELECT checkpoint_hi_inclusive FROM watermarks WHERE pipeline = ",
+        )
+        .bind::<Text, _>(DataPodEventHandler::NAME)
+        .sql(")"),
+    )
+}
+
+/// Cursor of the last event matching no filter, so that a new stream starts at the tip
+async fn latest_event_cursor(conn: &mut AsyncPgConnection) -> Result<Option<EventCursor>> {
+    use datapod_events::dsl as e;
+    let cursor = e::datapod_events
+        .filter(committed_events())
+        .select((e::checkpoint_sequence_number, e::transaction_index, e::event_index))
+        .order((
+            e::checkpoint_sequence_number.desc(),
+            e::transaction_index.desc(),
+            e::event_index.desc(),
+        ))
+        .first::<(i64, i64, i64)>(conn)
+        .await
+        .optional()?;
+
+    Ok(cursor.map(|(checkpoint, transaction_index, event_index)| EventCursor {
+        checkpoint,
+        transaction_index,
+        event_index,
+    }))
+}
+
+/// Events after `cursor` in chain order
+fn after_event(
+    cursor: EventCursor,
+) -> Box<dyn BoxableExpression<datapod_events::table, Pg, SqlType = Bool>> {
+    Box::new(
+        sql::<Bool>("(checkpoint_sequence_number, transaction_index, event_index) > (")
+            .bind::<BigInt, _>(cursor.checkpoint)
+            .sql(", ")
+            .bind::<BigInt, _>(cursor.transaction_index)
+            .sql(", ")
+            .bind::<BigInt, _>(cursor.event_index)
+            .sql(")"),
+    )
+}
+
+/// Up to `limit + 1` committed DataPod events matching `filter` after `after`
+pub async fn load_datapod_events(
+    conn: &mut AsyncPgConnection,
+    filter: &EventFilter,
+    after: Option<EventCursor>,
+    limit: i64,
+) -> Result<Vec<DataPodEventView>> {
+    use datapod_events::dsl as e;
+    let mut query = e::datapod_events
+        .filter(committed_events())
+        .select(DataPodEventView::as_select())
+        .order((e::checkpoint_sequence_number, e::transaction_index, e::event_index))
+        .limit(limit + 1)
+        .into_boxed();
+
+    if let Some(datapod_id) = filter.datapod_id {
+        query = query.filter(e::datapod_id.eq(datapod_id));
+    }
+    if let Some(event_type) = &filter.event_type {
+        query = query.filter(e::event_type.eq(event_type.clone()));
+    }
+    if let Some(seller) = filter.seller {
+        query = query.filter(e::seller.eq(seller));
+    }
+    if let Some(cursor) = after {
+        query = query.filter(after_event(cursor));
+    }
+
+    Ok(query.load(conn).await?)
+}
+
+async fn get_transaction(
+    State(state): State<ApiState>,
+    Path(digest): Path<Digest>,
+) -> ApiResult<TransactionView> {
+    let mut conn = state.connection().await?;
+    let transaction = transaction_digests::table
+        .filter(transaction_digests::tx_digest.eq(digest))
+        .select(TransactionView::as_select())
+        .first(&mut conn)
+        .await
+        .optional()?;
+
+    match transaction {
+        Some(transaction) => Ok(Json(transaction)),
+        None => Err(not_found(&mut conn, "transaction_digests").await?),
+    }
+}
+
+async fn get_object(
+    State(state): State<ApiState>,
+    Path(id): Path<Address>,
+) -> ApiResult<ObjectView> {
+    let mut conn = state.connection().await?;
+    smart_contract_objects::table
+        .filter(smart_contract_objects::object_id.eq(id))
+        .select(ObjectView::as_select())
+        .first(&mut conn)
+        .await
+        .optional()?
+        .map(Json)
+        .ok_or(ApiError::NotFound)
+}
+
+async fn list_pruned_tables(State(state): State<ApiState>) -> ApiResult<Vec<PrunedTableView>> {
+    let mut conn = state.connection().await?;
+    Ok(Json(load_pruned_tables(&mut conn).await?))
+}
+
+/// Tables the pruner has deleted old rows from, and how far
+pub async fn load_pruned_tables(conn: &mut AsyncPgConnection) -> Result<Vec<PrunedTableView>> {
+    Ok(pruner_watermarks::table
+        .select(PrunedTableView::as_select())
+        .order(pruner_watermarks::table_name)
+        .load(conn)
+        .await?)
+}
+
+/// The error for a row missing from `table`, which tells when the row may have been pruned
+async fn not_found(conn: &mut AsyncPgConnection, table: &str) -> Result<ApiError> {
+    let pruned_before = pruner_watermarks::table
+        .find(table)
+        .select(pruner_watermarks::pruned_before_checkpoint)
+        .first(conn)
+        .await
+        .optional()?;
+
+    Ok(pruned_before.map_or(ApiError::NotFound, ApiError::Pruned))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_cursors_round_trip() {
+        let cursor = EventCursor { checkpoint: 1200, transaction_index: 17, event_index: 3 };
+        assert_eq!(cursor.encode(), "1200:17:3");
+        assert_eq!(EventCursor::decode(&cursor.encode()).unwrap(), cursor);
+
+        let start = EventCursor::before_checkpoint(0);
+        assert_eq!(EventCursor::decode(&start.encode()).unwrap(), start);
+    }
+
+    #[test]
+    fn before_checkpoint_sorts_after_every_earlier_event() {
+        let start = EventCursor::before_checkpoint(10);
+        let key = |c: EventCursor| (c.checkpoint, c.transaction_index, c.event_index);
+        let last_of_9 = EventCursor { checkpoint: 9, transaction_index: 900, event_index: 500 };
+        let first_of_10 = EventCursor { checkpoint: 10, transaction_index: 0, event_index: 0 };
+        assert!(key(last_of_9) < key(start));
+        assert!(key(start) < key(first_of_10));
+    }
+
+    #[test]
+    fn malformed_cursors_are_bad_requests() {
+        let cursors = [
+            "",
+            "1",
+            "1:2",
+            "1:2:3:4",
+            "1:x:3",
+            "1::3",
+            " 1:2:3",
+            "1:2:99999999999999999999",
+        ];
+        for cursor in cursors {
+            assert!(
+                matches!(EventCursor::decode(cursor), Err(ApiError::BadRequest(_))),
+                "{cursor:?}",
+            );
+        }
+
+        assert!(matches!(id_cursor("0xzz"), Err(ApiError::BadRequest(_))));
+        assert_eq!(id_cursor("0x2").unwrap(), "0x02".parse().unwrap());
+    }
+}
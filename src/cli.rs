@@ -0,0 +1,540 @@
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use sui_indexer_alt_framework::{
+    cluster::Args,
+    pipeline::{concurrent::ConcurrentConfig, sequential::SequentialConfig},
+};
+use url::Url;
+
+use crate::models::Address;
+
+/// Command-line arguments: the framework's indexer arguments plus pipeline selection.
+/// Without a subcommand the binary runs the indexer.
+#[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub indexer: Args,
+
+    /// Only run these pipelines (comma separated). All pipelines run when omitted.
+    #[arg(long = "pipeline", value_enum, value_delimiter = ',')]
+    pub pipelines: Vec<Pipeline>,
+
+    /// Do not run these pipelines (comma separated). Applied after `--pipeline`.
+    #[arg(long = "skip-pipeline", value_enum, value_delimiter = ',')]
+    pub skip_pipelines: Vec<Pipeline>,
+
+    /// Run these pipelines as concurrent pipelines (comma separated), which commit checkpoints out
+    /// of order for faster backfills. Not smart-contract-objects or object-versions.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub concurrent: Vec<Pipeline>,
+
+    /// Which transactions the transaction-digests pipeline keeps
+    #[arg(long, value_enum, default_value_t = TransactionFilter::All)]
+    pub transaction_filter: TransactionFilter,
+
+    /// Issue `NOTIFY sourcenet_<pipeline>` when these pipelines commit (comma separated).
+    /// Every pipeline notifies when the flag is given without a value.
+    #[arg(long, value_enum, value_delimiter = ',', num_args = 0..)]
+    pub notify: Option<Vec<Pipeline>>,
+
+    /// Checkpoint lag, as `N` for every pipeline or `PIPELINE=N` for one. Repeatable.
+    #[arg(long, value_parser = parse_override::<u64>)]
+    pub checkpoint_lag: Vec<Override<u64>>,
+
+    /// Committer write concurrency, as `N` or `PIPELINE=N`. Repeatable.
+    #[arg(long, value_parser = parse_override::<usize>)]
+    pub write_concurrency: Vec<Override<usize>>,
+
+    /// Committer collect interval in milliseconds, as `N` or `PIPELINE=N`. Repeatable.
+    #[arg(long, value_parser = parse_override::<u64>)]
+    pub collect_interval_ms: Vec<Override<u64>>,
+
+    /// Committer watermark interval in milliseconds, as `N` or `PIPELINE=N`. Repeatable.
+    #[arg(long, value_parser = parse_override::<u64>)]
+    pub watermark_interval_ms: Vec<Override<u64>>,
+
+    /// Checkpoints covered by each partition the indexer creates for partitioned tables
+    #[arg(long, default_value_t = crate::partitions::DEFAULT_PARTITION_SIZE)]
+    pub partition_size: i64,
+
+    /// Prune a pipeline's table, as `PIPELINE=N` to keep the last N checkpoints or
+    /// `PIPELINE=<n>d` (or `h`, `m`, `s`) to keep rows that recent. Repeatable.
+    #[arg(long, value_parser = parse_retention)]
+    pub retention: Vec<RetentionPolicy>,
+
+    /// How often the pruner deletes rows past their retention, in milliseconds
+    #[arg(long, default_value_t = 60_000)]
+    pub prune_interval_ms: u64,
+
+    /// Rows the pruner deletes per statement, rounded up to a whole checkpoint
+    #[arg(long, default_value_t = 10_000)]
+    pub prune_batch_size: i64,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Serve the read-only HTTP API over the indexed tables
+    Serve(ServeArgs),
+
+    /// Manage webhook subscriptions and deliver queued webhooks
+    #[command(subcommand)]
+    Webhooks(WebhookCommand),
+
+    /// Consume the event outbox with per-group acknowledged offsets
+    #[command(subcommand)]
+    Outbox(OutboxCommand),
+
+    /// List, detach or drop checkpoint-range partitions of the append-only tables
+    #[command(subcommand)]
+    Partitions(PartitionCommand),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum PartitionCommand {
+    /// List partitions with their checkpoint range
+    List,
+
+    /// Detach the partitions of a table that only hold checkpoints before `before`, keeping
+    /// them as standalone tables
+    Detach {
+        #[arg(long, value_enum)]
+        table: PartitionedTable,
+
+        #[arg(long)]
+        before: i64,
+    },
+
+    /// Drop the partitions of a table that only hold checkpoints before `before`
+    Drop {
+        #[arg(long, value_enum)]
+        table: PartitionedTable,
+
+        #[arg(long)]
+        before: i64,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum OutboxCommand {
+    /// Print the events after the group's offset as JSON lines, without acknowledging them
+    Consume {
+        #[arg(long)]
+        group: String,
+
+        /// Most events to print
+        #[arg(long, default_value_t = 100)]
+        limit: i64,
+    },
+
+    /// Acknowledge every event up to and including `sequence` for the group
+    Ack {
+        #[arg(long)]
+        group: String,
+
+        sequence: i64,
+    },
+
+    /// List consumer groups with their offset and number of unacknowledged events
+    Groups,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum WebhookCommand {
+    /// Subscribe a URL to SourceNet events and print the subscription ID
+    Add(SubscribeArgs),
+
+    /// List webhook subscriptions
+    List,
+
+    /// Delete a subscription and its queued deliveries
+    Remove { id: i64 },
+
+    /// Requeue dead deliveries: those given, or every dead delivery when none are given
+    Retry { ids: Vec<i64> },
+
+    /// Deliver queued webhooks until stopped
+    Deliver(DeliverArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct SubscribeArgs {
+    /// Endpoint deliveries are POSTed to
+    #[arg(long)]
+    pub url: Url,
+
+    /// Key of the HMAC-SHA256 signature sent with each delivery
+    #[arg(long)]
+    pub secret: String,
+
+    /// Only deliver events of this type, e.g. `PurchaseCreated`
+    #[arg(long)]
+    pub event_type: Option<String>,
+
+    /// Only deliver events naming this seller
+    #[arg(long)]
+    pub seller: Option<Address>,
+
+    /// Only deliver events of this DataPod, by object ID or application-level ID
+    #[arg(long)]
+    pub datapod_id: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DeliverArgs {
+    /// Deliveries sent at once
+    #[arg(long, default_value_t = 32)]
+    pub batch_size: i64,
+
+    /// How often to check for due deliveries when none are left, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    pub poll_interval_ms: u64,
+
+    /// Timeout of each webhook request, in milliseconds
+    #[arg(long, default_value_t = 10_000)]
+    pub request_timeout_ms: u64,
+
+    /// Attempts after which a delivery is moved to the dead-letter state
+    #[arg(long, default_value_t = 10)]
+    pub max_attempts: i32,
+
+    /// Delay before the first retry, doubled after each failed attempt, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    pub initial_backoff_ms: u64,
+
+    /// Longest delay between retries, in milliseconds
+    #[arg(long, default_value_t = 3_600_000)]
+    pub max_backoff_ms: u64,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ServeArgs {
+    /// Address the HTTP API listens on
+    #[arg(long, default_value = "0.0.0.0:8080")]
+    pub listen_address: SocketAddr,
+
+    /// Page size of list endpoints when the request does not set `limit`
+    #[arg(long, default_value_t = 50)]
+    pub default_page_size: i64,
+
+    /// Largest `limit` a request may ask for
+    #[arg(long, default_value_t = 200)]
+    pub max_page_size: i64,
+
+    /// How often event streams check for newly committed events, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    pub stream_poll_interval_ms: u64,
+
+    /// Deepest nesting of fields a GraphQL query may select
+    #[arg(long, default_value_t = 10)]
+    pub graphql_max_depth: usize,
+
+    /// Highest complexity of a GraphQL query: each field counts once, multiplied by `first`
+    /// for the fields under a list
+    #[arg(long, default_value_t = 1000)]
+    pub graphql_max_complexity: usize,
+}
+
+/// Pipelines this binary can run
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pipeline {
+    TransactionDigests,
+    DatapodEvents,
+    PurchaseEvents,
+    EscrowEvents,
+    SmartContractObjects,
+    ObjectVersions,
+    MoveCalls,
+    FailedTransactions,
+}
+
+/// Tables partitioned by checkpoint range
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionedTable {
+    DatapodEvents,
+    TransactionDigests,
+    ObjectVersions,
+}
+
+impl PartitionedTable {
+    /// Name of the partitioned (parent) table
+    pub fn name(self) -> &'static str {
+        match self {
+            PartitionedTable::DatapodEvents => "datapod_events",
+            PartitionedTable::TransactionDigests => "transaction_digests",
+            PartitionedTable::ObjectVersions => "object_versions",
+        }
+    }
+}
+
+impl Pipeline {
+    /// Whether the pipeline can commit checkpoints in any order. Event pipelines qualify: their
+    /// current-state tables are then kept by a separate sequential pipeline. Object versions do
+    /// not, since a deleted version can take its type from an earlier version.
+    pub fn can_run_concurrently(self) -> bool {
+        !matches!(self, Pipeline::SmartContractObjects | Pipeline::ObjectVersions)
+    }
+}
+
+/// Which transactions count as SourceNet transactions
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionFilter {
+    /// Every transaction on the network
+    All,
+    /// Transactions whose programmable transaction calls into the package
+    Calls,
+    /// Transactions that emit an event defined in the package
+    Events,
+    /// Transactions that read or write an object whose type is defined in the package
+    Objects,
+    /// Transactions matching any of `calls`, `events` or `objects`
+    #[value(name = "sourcenet")]
+    SourceNet,
+}
+
+/// How long the pruner keeps the rows of a table
+#[derive(Debug, Clone, Copy)]
+pub enum Retention {
+    /// Rows of the last N checkpoints the pipeline committed
+    Checkpoints(i64),
+    /// Rows whose checkpoint timestamp is at most this old
+    Age(Duration),
+}
+
+/// Retention of the table a pipeline appends to
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionPolicy {
+    pub pipeline: Pipeline,
+    pub retention: Retention,
+}
+
+/// A setting that applies to every pipeline, or to a single one when `pipeline` is set
+#[derive(Debug, Clone)]
+pub struct Override<T> {
+    pipeline: Option<Pipeline>,
+    value: T,
+}
+
+impl Cli {
+    /// Whether `pipeline` needs to know the SourceNet package
+    pub fn requires_package(&self, pipeline: Pipeline) -> bool {
+        match pipeline {
+            Pipeline::TransactionDigests => self.transaction_filter != TransactionFilter::All,
+            _ => true,
+        }
+    }
+
+    /// Pipelines to register, in declaration order
+    pub fn enabled_pipelines(&self) -> Vec<Pipeline> {
+        Pipeline::value_variants()
+            .iter()
+            .copied()
+            .filter(|p| self.pipelines.is_empty() || self.pipelines.contains(p))
+            .filter(|p| !self.skip_pipelines.contains(p))
+            .collect()
+    }
+
+    /// Pipelines that notify when they commit
+    pub fn notifying_pipelines(&self) -> Vec<Pipeline> {
+        match &self.notify {
+            None => vec![],
+            Some(pipelines) if pipelines.is_empty() => Pipeline::value_variants().to_vec(),
+            Some(pipelines) => pipelines.clone(),
+        }
+    }
+
+    /// Sequential pipeline configuration for `pipeline`, with command-line overrides applied
+    pub fn sequential_config(&self, pipeline: Pipeline) -> SequentialConfig {
+        let mut config = SequentialConfig::default();
+
+        if let Some(lag) = resolve(&self.checkpoint_lag, pipeline) {
+            config.checkpoint_lag = lag;
+        }
+        if let Some(concurrency) = resolve(&self.write_concurrency, pipeline) {
+            config.committer.write_concurrency = concurrency;
+        }
+        if let Some(interval) = resolve(&self.collect_interval_ms, pipeline) {
+            config.committer.collect_interval_ms = interval;
+        }
+        if let Some(interval) = resolve(&self.watermark_interval_ms, pipeline) {
+            config.committer.watermark_interval_ms = interval;
+        }
+
+        config
+    }
+
+    /// Concurrent pipeline configuration for `pipeline`, with command-line overrides applied,
+    /// or `None` when it runs as a sequential pipeline. Concurrent pipelines cannot lag, so a
+    /// `--checkpoint-lag` that applies to one is an error rather than ignored.
+    pub fn concurrent_config(&self, pipeline: Pipeline) -> Result<Option<ConcurrentConfig>> {
+        if !self.concurrent.contains(&pipeline) {
+            return Ok(None);
+        }
+
+        if let Some(lag) = resolve(&self.checkpoint_lag, pipeline) {
+            let name = pipeline.to_possible_value().map(|value| value.get_name().to_string());
+            bail!(
+                "--checkpoint-lag {lag} applies to {}, which runs concurrently and cannot lag. \
+                 Give the lag per pipeline, for sequential pipelines only.",
+                name.unwrap_or_default(),
+            );
+        }
+
+        let mut config = ConcurrentConfig::default();
+
+        if let Some(concurrency) = resolve(&self.write_concurrency, pipeline) {
+            config.committer.write_concurrency = concurrency;
+        }
+        if let Some(interval) = resolve(&self.collect_interval_ms, pipeline) {
+            config.committer.collect_interval_ms = interval;
+        }
+        if let Some(interval) = resolve(&self.watermark_interval_ms, pipeline) {
+            config.committer.watermark_interval_ms = interval;
+        }
+
+        Ok(Some(config))
+    }
+}
+
+/// A pipeline-specific override beats a global one; otherwise the last one given wins
+fn resolve<T: Copy>(overrides: &[Override<T>], pipeline: Pipeline) -> Option<T> {
+    let last = |specific: bool| {
+        overrides
+            .iter()
+            .rev()
+            .find(|o| o.pipeline.is_some() == specific && o.pipeline.is_none_or(|p| p == pipeline))
+            .map(|o| o.value)
+    };
+
+    last(true).or_else(|| last(false))
+}
+
+fn parse_override<T>(s: &str) -> Result<Override<T>, String>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let (pipeline, value) = match s.split_once('=') {
+        Some((name, value)) => (Some(<Pipeline as ValueEnum>::from_str(name, true)?), value),
+        None => (None, s),
+    };
+
+    let value = value
+        .parse()
+        .map_err(|e| format!("invalid value {value:?}: {e}"))?;
+
+    Ok(Override { pipeline, value })
+}
+
+fn parse_retention(s: &str) -> Result<RetentionPolicy, String> {
+    let (name, spec) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected PIPELINE=RETENTION, got {s:?}"))?;
+    let pipeline = <Pipeline as ValueEnum>::from_str(name, true)?;
+    let table = crate::pruner::table(pipeline)
+        .ok_or_else(|| format!("{name} keeps current state and cannot be pruned"))?;
+
+    let digits = spec.find(|c: char| !c.is_ascii_digit()).unwrap_or(spec.len());
+    let (count, unit) = spec.split_at(digits);
+    let count: u64 = count
+        .parse()
+        .map_err(|e| format!("invalid retention {spec:?}: {e}"))?;
+
+    let seconds = match unit {
+        "" => {
+            let checkpoints = i64::try_from(count)
+                .map_err(|e| format!("invalid retention {spec:?}: {e}"))?;
+            let retention = Retention::Checkpoints(checkpoints);
+            return Ok(RetentionPolicy { pipeline, retention });
+        }
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(format!("invalid retention {spec:?}: unit must be d, h, m or s")),
+    };
+
+    if table.timestamp.is_none() {
+        return Err(format!("{} has no timestamp, retain it by checkpoints", table.name));
+    }
+
+    let age = Duration::from_secs(count.saturating_mul(seconds));
+    Ok(RetentionPolicy { pipeline, retention: Retention::Age(age) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(values: &[&str]) -> Vec<Override<u64>> {
+        values.iter().map(|s| parse_override(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn pipeline_specific_overrides_beat_global_ones() {
+        let lag = overrides(&["10", "datapod-events=0", "20"]);
+        assert_eq!(resolve(&lag, Pipeline::DatapodEvents), Some(0));
+        assert_eq!(resolve(&lag, Pipeline::MoveCalls), Some(20));
+
+        let lag = overrides(&["purchase-events=3", "PURCHASE-EVENTS=4"]);
+        assert_eq!(resolve(&lag, Pipeline::PurchaseEvents), Some(4));
+        assert_eq!(resolve(&lag, Pipeline::EscrowEvents), None);
+        assert_eq!(resolve::<u64>(&[], Pipeline::EscrowEvents), None);
+    }
+
+    #[test]
+    fn malformed_overrides_are_rejected() {
+        assert!(parse_override::<u64>("no-such-pipeline=1").is_err());
+        assert!(parse_override::<u64>("move-calls=soon").is_err());
+        assert!(parse_override::<u64>("-1").is_err());
+    }
+
+    #[test]
+    fn retention_is_checkpoints_or_an_age() {
+        let policy = parse_retention("datapod-events=1000").unwrap();
+        assert_eq!(policy.pipeline, Pipeline::DatapodEvents);
+        assert!(matches!(policy.retention, Retention::Checkpoints(1000)));
+
+        for (spec, seconds) in [("30s", 30), ("15m", 900), ("2h", 7200), ("7d", 604_800)] {
+            let policy = parse_retention(&format!("move-calls={spec}")).unwrap();
+            assert_eq!(policy.pipeline, Pipeline::MoveCalls);
+            assert!(
+                matches!(policy.retention, Retention::Age(age) if age.as_secs() == seconds),
+                "{spec}: {policy:?}",
+            );
+        }
+
+        // Checkpoint counts are stored as BIGINT, while ages saturate
+        assert!(parse_retention(&format!("move-calls={}", u64::MAX)).is_err());
+        let policy = parse_retention(&format!("move-calls={}d", u64::MAX)).unwrap();
+        assert!(matches!(policy.retention, Retention::Age(age) if age.as_secs() == u64::MAX));
+    }
+
+    #[test]
+    fn invalid_retention_is_rejected() {
+        for spec in [
+            "1000",
+            "datapod-events",
+            "datapod-events=",
+            "datapod-events=d",
+            "datapod-events=7w",
+            "datapod-events=-7d",
+            "no-such-pipeline=7d",
+            // Keeps current state
+            "smart-contract-objects=1000",
+            // Has no timestamp to measure age by
+            "object-versions=7d",
+        ] {
+            assert!(parse_retention(spec).is_err(), "{spec}");
+        }
+
+        assert!(parse_retention("object-versions=1000").is_ok());
+    }
+}
@@ -0,0 +1,357 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sui_types::base_types::SuiAddress;
+
+/// Mirror of `sourcenet::datapod::DataPodCreated`
+#[derive(Deserialize, Debug, Clone)]
+pub struct DataPodCreated {
+    pub datapod_id: SuiAddress,
+    pub seller: SuiAddress,
+    pub title: String,
+    pub category: String,
+    pub price_sui: u64,
+}
+
+/// Mirror of `sourcenet::datapod::DataPodPublished`
+#[derive(Deserialize, Debug, Clone)]
+pub struct DataPodPublished {
+    pub datapod_id: SuiAddress,
+    pub seller: SuiAddress,
+    pub title: String,
+    pub category: String,
+    pub price_sui: u64,
+    pub kiosk_id: String,
+}
+
+/// Mirror of `sourcenet::datapod::DataPodDelisted`
+#[derive(Deserialize, Debug, Clone)]
+pub struct DataPodDelisted {
+    pub datapod_id: SuiAddress,
+    pub seller: SuiAddress,
+}
+
+/// Mirror of `sourcenet::datapod::DataPodPriceUpdated`
+#[derive(Deserialize, Debug, Clone)]
+pub struct DataPodPriceUpdated {
+    pub datapod_id: SuiAddress,
+    pub old_price: u64,
+    pub new_price: u64,
+}
+
+/// Any event emitted by the `sourcenet::datapod` module
+#[derive(Debug, Clone)]
+pub enum DataPodEvent {
+    Created(DataPodCreated),
+    Published(DataPodPublished),
+    Delisted(DataPodDelisted),
+    PriceUpdated(DataPodPriceUpdated),
+}
+
+impl DataPodEvent {
+    /// Decode the BCS `contents` of an event whose struct name is `name`.
+    /// Returns `None` for structs that are not DataPod events.
+    pub fn decode(name: &str, contents: &[u8]) -> Result<Option<Self>> {
+        let event = match name {
+            "DataPodCreated" => Self::Created(decode(name, contents)?),
+            "DataPodPublished" => Self::Published(decode(name, contents)?),
+            "DataPodDelisted" => Self::Delisted(decode(name, contents)?),
+            "DataPodPriceUpdated" => Self::PriceUpdated(decode(name, contents)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+
+    /// Name of the Move struct this event was decoded from
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "DataPodCreated",
+            Self::Published(_) => "DataPodPublished",
+            Self::Delisted(_) => "DataPodDelisted",
+            Self::PriceUpdated(_) => "DataPodPriceUpdated",
+        }
+    }
+}
+
+/// Mirror of `sourcenet::purchase::PurchaseCreated`
+#[derive(Deserialize, Debug, Clone)]
+pub struct PurchaseCreated {
+    pub purchase_id: SuiAddress,
+    pub datapod_id: String,
+    pub buyer: SuiAddress,
+    pub seller: SuiAddress,
+    pub price_sui: u64,
+}
+
+/// Mirror of `sourcenet::purchase::PurchaseCompleted`
+#[derive(Deserialize, Debug, Clone)]
+pub struct PurchaseCompleted {
+    pub purchase_id: SuiAddress,
+    pub buyer: SuiAddress,
+    pub seller: SuiAddress,
+    pub price_sui: u64,
+}
+
+/// Mirror of `sourcenet::purchase::PurchaseRefunded`
+#[derive(Deserialize, Debug, Clone)]
+pub struct PurchaseRefunded {
+    pub purchase_id: SuiAddress,
+    pub buyer: SuiAddress,
+    pub price_sui: u64,
+}
+
+/// Mirror of `sourcenet::purchase::PurchaseDisputed`
+#[derive(Deserialize, Debug, Clone)]
+pub struct PurchaseDisputed {
+    pub purchase_id: SuiAddress,
+    pub buyer: SuiAddress,
+    pub seller: SuiAddress,
+}
+
+/// Any event emitted by the `sourcenet::purchase` module
+#[derive(Debug, Clone)]
+pub enum PurchaseEvent {
+    Created(PurchaseCreated),
+    Completed(PurchaseCompleted),
+    Refunded(PurchaseRefunded),
+    Disputed(PurchaseDisputed),
+}
+
+impl PurchaseEvent {
+    /// Decode the BCS `contents` of an event whose struct name is `name`.
+    /// Returns `None` for structs that are not Purchase events.
+    pub fn decode(name: &str, contents: &[u8]) -> Result<Option<Self>> {
+        let event = match name {
+            "PurchaseCreated" => Self::Created(decode(name, contents)?),
+            "PurchaseCompleted" => Self::Completed(decode(name, contents)?),
+            "PurchaseRefunded" => Self::Refunded(decode(name, contents)?),
+            "PurchaseDisputed" => Self::Disputed(decode(name, contents)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+
+    /// Name of the Move struct this event was decoded from
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "PurchaseCreated",
+            Self::Completed(_) => "PurchaseCompleted",
+            Self::Refunded(_) => "PurchaseRefunded",
+            Self::Disputed(_) => "PurchaseDisputed",
+        }
+    }
+}
+
+/// Mirror of `sourcenet::escrow::EscrowCreated`
+#[derive(Deserialize, Debug, Clone)]
+pub struct EscrowCreated {
+    pub escrow_id: SuiAddress,
+    pub purchase_id: String,
+    pub buyer: SuiAddress,
+    pub seller: SuiAddress,
+    pub amount: u64,
+}
+
+/// Mirror of `sourcenet::escrow::EscrowReleased`
+#[derive(Deserialize, Debug, Clone)]
+pub struct EscrowReleased {
+    pub escrow_id: SuiAddress,
+    pub seller: SuiAddress,
+    pub amount: u64,
+}
+
+/// Mirror of `sourcenet::escrow::EscrowRefunded`
+#[derive(Deserialize, Debug, Clone)]
+pub struct EscrowRefunded {
+    pub escrow_id: SuiAddress,
+    pub buyer: SuiAddress,
+    pub amount: u64,
+}
+
+/// Any event emitted by the `sourcenet::escrow` module
+#[derive(Debug, Clone)]
+pub enum EscrowEvent {
+    Created(EscrowCreated),
+    Released(EscrowReleased),
+    Refunded(EscrowRefunded),
+}
+
+impl EscrowEvent {
+    /// Decode the BCS `contents` of an event whose struct name is `name`.
+    /// Returns `None` for structs that are not Escrow events.
+    pub fn decode(name: &str, contents: &[u8]) -> Result<Option<Self>> {
+        let event = match name {
+            "EscrowCreated" => Self::Created(decode(name, contents)?),
+            "EscrowReleased" => Self::Released(decode(name, contents)?),
+            "EscrowRefunded" => Self::Refunded(decode(name, contents)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+
+    /// Name of the Move struct this event was decoded from
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "EscrowCreated",
+            Self::Released(_) => "EscrowReleased",
+            Self::Refunded(_) => "EscrowRefunded",
+        }
+    }
+}
+
+fn decode<'de, T: Deserialize<'de>>(name: &str, contents: &'de [u8]) -> Result<T> {
+    bcs::from_bytes(contents).with_context(|| format!("Failed to deserialize {name} event"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(last: u8) -> SuiAddress {
+        let mut bytes = [0; 32];
+        bytes[31] = last;
+        SuiAddress::from_bytes(bytes).unwrap()
+    }
+
+    /// BCS encoding of a Move struct with these fields, in order
+    fn contents<T: serde::Serialize>(fields: T) -> Vec<u8> {
+        bcs::to_bytes(&fields).unwrap()
+    }
+
+    #[test]
+    fn datapod_events_decode() {
+        let bytes = contents((address(1), address(2), "Weather", "climate", 10u64));
+        let Some(DataPodEvent::Created(event)) =
+            DataPodEvent::decode("DataPodCreated", &bytes).unwrap()
+        else {
+            panic!("not a DataPodCreated event");
+        };
+        assert_eq!(event.datapod_id, address(1));
+        assert_eq!(event.seller, address(2));
+        assert_eq!(event.title, "Weather");
+        assert_eq!(event.category, "climate");
+        assert_eq!(event.price_sui, 10);
+
+        let bytes = contents((address(1), address(2), "Weather", "climate", u64::MAX, "0xk"));
+        let Some(DataPodEvent::Published(event)) =
+            DataPodEvent::decode("DataPodPublished", &bytes).unwrap()
+        else {
+            panic!("not a DataPodPublished event");
+        };
+        assert_eq!(event.price_sui, u64::MAX);
+        assert_eq!(event.kiosk_id, "0xk");
+
+        let bytes = contents((address(1), address(2)));
+        let Some(DataPodEvent::Delisted(event)) =
+            DataPodEvent::decode("DataPodDelisted", &bytes).unwrap()
+        else {
+            panic!("not a DataPodDelisted event");
+        };
+        assert_eq!(event.datapod_id, address(1));
+        assert_eq!(event.seller, address(2));
+
+        let bytes = contents((address(1), 10u64, 12u64));
+        let Some(DataPodEvent::PriceUpdated(event)) =
+            DataPodEvent::decode("DataPodPriceUpdated", &bytes).unwrap()
+        else {
+            panic!("not a DataPodPriceUpdated event");
+        };
+        assert_eq!(event.datapod_id, address(1));
+        assert_eq!((event.old_price, event.new_price), (10, 12));
+    }
+
+    #[test]
+    fn purchase_events_decode() {
+        let bytes = contents((address(1), "0xd", address(2), address(3), 10u64));
+        let Some(PurchaseEvent::Created(event)) =
+            PurchaseEvent::decode("PurchaseCreated", &bytes).unwrap()
+        else {
+            panic!("not a PurchaseCreated event");
+        };
+        assert_eq!(event.purchase_id, address(1));
+        assert_eq!(event.datapod_id, "0xd");
+        assert_eq!(event.buyer, address(2));
+        assert_eq!(event.seller, address(3));
+        assert_eq!(event.price_sui, 10);
+
+        let bytes = contents((address(1), address(2), address(3), 10u64));
+        let Some(PurchaseEvent::Completed(event)) =
+            PurchaseEvent::decode("PurchaseCompleted", &bytes).unwrap()
+        else {
+            panic!("not a PurchaseCompleted event");
+        };
+        assert_eq!((event.buyer, event.seller, event.price_sui), (address(2), address(3), 10));
+
+        let bytes = contents((address(1), address(2), 10u64));
+        let Some(PurchaseEvent::Refunded(event)) =
+            PurchaseEvent::decode("PurchaseRefunded", &bytes).unwrap()
+        else {
+            panic!("not a PurchaseRefunded event");
+        };
+        assert_eq!((event.buyer, event.price_sui), (address(2), 10));
+
+        let bytes = contents((address(1), address(2), address(3)));
+        let Some(PurchaseEvent::Disputed(event)) =
+            PurchaseEvent::decode("PurchaseDisputed", &bytes).unwrap()
+        else {
+            panic!("not a PurchaseDisputed event");
+        };
+        assert_eq!((event.buyer, event.seller), (address(2), address(3)));
+    }
+
+    #[test]
+    fn escrow_events_decode() {
+        let bytes = contents((address(1), "0xp", address(2), address(3), 10u64));
+        let Some(EscrowEvent::Created(event)) =
+            EscrowEvent::decode("EscrowCreated", &bytes).unwrap()
+        else {
+            panic!("not an EscrowCreated event");
+        };
+        assert_eq!(event.escrow_id, address(1));
+        assert_eq!(event.purchase_id, "0xp");
+        assert_eq!(event.buyer, address(2));
+        assert_eq!(event.seller, address(3));
+        assert_eq!(event.amount, 10);
+
+        let bytes = contents((address(1), address(3), 10u64));
+        let Some(EscrowEvent::Released(event)) =
+            EscrowEvent::decode("EscrowReleased", &bytes).unwrap()
+        else {
+            panic!("not an EscrowReleased event");
+        };
+        assert_eq!((event.seller, event.amount), (address(3), 10));
+
+        let bytes = contents((address(1), address(2), 10u64));
+        let Some(EscrowEvent::Refunded(event)) =
+            EscrowEvent::decode("EscrowRefunded", &bytes).unwrap()
+        else {
+            panic!("not an EscrowRefunded event");
+        };
+        assert_eq!((event.buyer, event.amount), (address(2), 10));
+    }
+
+    #[test]
+    fn other_structs_are_not_events() {
+        let bytes = contents((address(1), address(2)));
+        assert!(DataPodEvent::decode("DataPodArchived", &bytes).unwrap().is_none());
+        assert!(DataPodEvent::decode("PurchaseCreated", &bytes).unwrap().is_none());
+        assert!(PurchaseEvent::decode("DataPodDelisted", &bytes).unwrap().is_none());
+        assert!(EscrowEvent::decode("EscrowDisputed", &bytes).unwrap().is_none());
+    }
+
+    #[test]
+    fn truncated_contents_are_errors() {
+        let bytes = contents((address(1), address(2), "Weather", "climate", 10u64));
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(DataPodEvent::decode("DataPodCreated", truncated).is_err());
+        assert!(DataPodEvent::decode("DataPodCreated", &[]).is_err());
+
+        let bytes = contents((address(1), address(2), 10u64));
+        assert!(PurchaseEvent::decode("PurchaseRefunded", &bytes[..40]).is_err());
+        assert!(EscrowEvent::decode("EscrowRefunded", &bytes[..40]).is_err());
+
+        // Trailing bytes mean the layout does not match either
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(EscrowEvent::decode("EscrowRefunded", &extended).is_err());
+    }
+}
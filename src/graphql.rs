@@ -0,0 +1,700 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_graphql::{
+    connection::{Connection, Edge},
+    dataloader::{DataLoader, Loader},
+    http::GraphiQLSource,
+    ComplexObject, Context, EmptyMutation, EmptySubscription, InputObject, Object, OutputType,
+    Schema,
+};
+use async_graphql_axum::GraphQL;
+use axum::{response::Html, routing::get, Router};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Bool, Nullable, Text};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::api::{
+    load_datapod_events, load_datapods, load_pruned_tables, ApiState, DataPodEventView,
+    DataPodFilter, DataPodView, EscrowView, EventCursor, EventFilter, PrunedTableView,
+    PurchaseView, TransactionView,
+};
+use crate::models::{Address, Digest};
+use crate::package::{DATAPOD_MODULE, PURCHASE_MODULE};
+use crate::schema::{datapods, escrows, purchases, smart_contract_objects, transaction_digests};
+
+pub type SourceNetSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// Page size assumed when estimating the complexity of a list that does not set `first`, the
+/// default of `--default-page-size`
+const ASSUMED_PAGE_SIZE: i32 = 50;
+
+/// Queries nested deeper than `max_depth`, or more complex than `max_complexity`, are rejected
+/// before they run
+pub fn schema(state: ApiState, max_depth: usize, max_complexity: usize) -> SourceNetSchema {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(DbLoader(state.clone()), tokio::spawn))
+        .data(state)
+        .limit_depth(max_depth)
+        .limit_complexity(max_complexity)
+        .finish()
+}
+
+/// `POST /graphql` executes queries, `GET /graphql` serves GraphiQL
+pub fn router(schema: SourceNetSchema) -> Router {
+    Router::new().route("/graphql", get(graphiql).post_service(GraphQL::new(schema)))
+}
+
+/// Complexity of a list field: that of one item, times the number of items asked for
+fn page_complexity(first: Option<i32>, child_complexity: usize) -> usize {
+    let items = first.unwrap_or(ASSUMED_PAGE_SIZE).max(1) as usize;
+    items.saturating_mul(child_complexity)
+}
+
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+/// Filters accepted by purchase connections
+#[derive(InputObject, Debug, Default)]
+pub struct PurchaseFilter {
+    pub buyer: Option<Address>,
+    pub seller: Option<Address>,
+    pub status: Option<String>,
+}
+
+/// Filters accepted by escrow connections
+#[derive(InputObject, Debug, Default)]
+pub struct EscrowFilter {
+    pub buyer: Option<Address>,
+    pub seller: Option<Address>,
+    pub status: Option<String>,
+}
+
+/// An address that lists DataPods
+pub struct Seller {
+    address: Address,
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn datapod(
+        &self,
+        ctx: &Context<'_>,
+        id: Address,
+    ) -> async_graphql::Result<Option<DataPodView>> {
+        let mut conn = ctx.data::<ApiState>()?.connection().await?;
+        Ok(find_datapod(&mut conn, id).await?)
+    }
+
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn datapods(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<DataPodFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, DataPodView>> {
+        datapod_connection(ctx, filter.unwrap_or_default(), first, after).await
+    }
+
+    async fn purchase(
+        &self,
+        ctx: &Context<'_>,
+        id: Address,
+    ) -> async_graphql::Result<Option<PurchaseView>> {
+        let mut conn = ctx.data::<ApiState>()?.connection().await?;
+        Ok(find_purchase(&mut conn, id).await?)
+    }
+
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn purchases(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<PurchaseFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, PurchaseView>> {
+        purchase_connection(ctx, filter.unwrap_or_default(), None, first, after).await
+    }
+
+    async fn escrow(
+        &self,
+        ctx: &Context<'_>,
+        id: Address,
+    ) -> async_graphql::Result<Option<EscrowView>> {
+        let mut conn = ctx.data::<ApiState>()?.connection().await?;
+        Ok(escrows::table
+            .find(id)
+            .select(EscrowView::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?)
+    }
+
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn escrows(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EscrowFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, EscrowView>> {
+        escrow_connection(ctx, filter.unwrap_or_default(), first, after).await
+    }
+
+    async fn seller(&self, address: Address) -> Seller {
+        Seller { address }
+    }
+
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        digest: Digest,
+    ) -> async_graphql::Result<Option<TransactionView>> {
+        let mut conn = ctx.data::<ApiState>()?.connection().await?;
+        Ok(find_transaction(&mut conn, digest).await?)
+    }
+
+    /// Tables whose old rows were pruned, with the first checkpoint still available
+    async fn pruned_tables(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<PrunedTableView>> {
+        let mut conn = ctx.data::<ApiState>()?.connection().await?;
+        Ok(load_pruned_tables(&mut conn).await?)
+    }
+
+    /// DataPod events in chain order: by checkpoint, transaction and index within the transaction
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        datapod_id: Option<Address>,
+        event_type: Option<String>,
+        seller: Option<Address>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, DataPodEventView>> {
+        let filter = EventFilter {
+            event_type,
+            seller,
+            datapod_id,
+        };
+        event_connection(ctx, filter, first, after).await
+    }
+}
+
+#[ComplexObject]
+impl DataPodView {
+    async fn seller_account(&self) -> Option<Seller> {
+        self.seller.map(|address| Seller { address })
+    }
+
+    /// Purchases of this listing, whether they refer to it by object ID or by its
+    /// application-level `datapod_id`
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn purchases(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<PurchaseFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, PurchaseView>> {
+        let ids = aliases(ctx, self.datapod_id, "datapod_id").await?;
+        purchase_connection(ctx, filter.unwrap_or_default(), Some(ids), first, after).await
+    }
+
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        event_type: Option<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, DataPodEventView>> {
+        let filter = EventFilter {
+            event_type,
+            datapod_id: Some(self.datapod_id),
+            ..Default::default()
+        };
+        event_connection(ctx, filter, first, after).await
+    }
+}
+
+#[ComplexObject]
+impl PurchaseView {
+    async fn datapod(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<DataPodView>> {
+        let Some(id) = &self.datapod_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data::<DataLoader<DbLoader>>()?;
+        if let Ok(object_id) = id.parse() {
+            if let Some(datapod) = loader.load_one(DataPodKey(object_id)).await? {
+                return Ok(Some(datapod));
+            }
+        }
+
+        let key = FieldKey {
+            module: DATAPOD_MODULE,
+            name: "DataPod",
+            field: "datapod_id",
+            value: id.clone(),
+        };
+        match loader.load_one(key).await? {
+            Some(object_id) => Ok(loader.load_one(DataPodKey(object_id)).await?),
+            None => Ok(None),
+        }
+    }
+
+    async fn seller_account(&self) -> Option<Seller> {
+        self.seller.map(|address| Seller { address })
+    }
+
+    /// The escrow holding the payment, referring to this purchase by object ID or by its
+    /// application-level `purchase_id`
+    async fn escrow(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<EscrowView>> {
+        let ids = aliases(ctx, self.purchase_id, "purchase_id").await?;
+        let escrows = ctx
+            .data::<DataLoader<DbLoader>>()?
+            .load_many(ids.into_iter().map(EscrowKey))
+            .await?;
+        Ok(escrows.into_values().min_by(|a, b| a.escrow_id.cmp(&b.escrow_id)))
+    }
+}
+
+#[ComplexObject]
+impl EscrowView {
+    async fn purchase(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<PurchaseView>> {
+        let Some(id) = &self.purchase_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data::<DataLoader<DbLoader>>()?;
+        if let Ok(object_id) = id.parse() {
+            if let Some(purchase) = loader.load_one(PurchaseKey(object_id)).await? {
+                return Ok(Some(purchase));
+            }
+        }
+
+        let key = FieldKey {
+            module: PURCHASE_MODULE,
+            name: "PurchaseRequest",
+            field: "purchase_id",
+            value: id.clone(),
+        };
+        match loader.load_one(key).await? {
+            Some(object_id) => Ok(loader.load_one(PurchaseKey(object_id)).await?),
+            None => Ok(None),
+        }
+    }
+}
+
+#[ComplexObject]
+impl DataPodEventView {
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<TransactionView>> {
+        let loader = ctx.data::<DataLoader<DbLoader>>()?;
+        Ok(loader.load_one(self.transaction_digest).await?)
+    }
+}
+
+#[Object]
+impl Seller {
+    async fn address(&self) -> Address {
+        self.address
+    }
+
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn datapods(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<DataPodFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, DataPodView>> {
+        let filter = DataPodFilter {
+            seller: Some(self.address),
+            ..filter.unwrap_or_default()
+        };
+        datapod_connection(ctx, filter, first, after).await
+    }
+
+    /// Purchases of this seller's listings
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn sales(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, PurchaseView>> {
+        let filter = PurchaseFilter {
+            seller: Some(self.address),
+            status,
+            ..Default::default()
+        };
+        purchase_connection(ctx, filter, None, first, after).await
+    }
+
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn escrows(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, EscrowView>> {
+        let filter = EscrowFilter {
+            seller: Some(self.address),
+            status,
+            ..Default::default()
+        };
+        escrow_connection(ctx, filter, first, after).await
+    }
+}
+
+/// Build a connection from up to `limit + 1` rows; the extra row only signals that more exist
+fn connection<T: OutputType>(
+    mut rows: Vec<T>,
+    limit: i64,
+    has_previous: bool,
+    cursor: impl Fn(&T) -> String,
+) -> Connection<String, T> {
+    let has_next = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+
+    let mut connection = Connection::new(has_previous, has_next);
+    connection
+        .edges
+        .extend(rows.into_iter().map(|row| Edge::new(cursor(&row), row)));
+    connection
+}
+
+async fn datapod_connection(
+    ctx: &Context<'_>,
+    filter: DataPodFilter,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, DataPodView>> {
+    let state = ctx.data::<ApiState>()?;
+    let limit = state.limit(first.map(i64::from));
+    let after = id_cursor(after.as_deref())?;
+    let mut conn = state.connection().await?;
+    let rows = load_datapods(&mut conn, &filter, after, limit).await?;
+    Ok(connection(rows, limit, after.is_some(), |row| row.datapod_id.to_string()))
+}
+
+async fn purchase_connection(
+    ctx: &Context<'_>,
+    filter: PurchaseFilter,
+    datapod_ids: Option<Vec<String>>,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, PurchaseView>> {
+    let state = ctx.data::<ApiState>()?;
+    let limit = state.limit(first.map(i64::from));
+    let after = id_cursor(after.as_deref())?;
+    let mut conn = state.connection().await?;
+
+    let mut query = purchases::table
+        .select(PurchaseView::as_select())
+        .order(purchases::purchase_id)
+        .limit(limit + 1)
+        .into_boxed();
+
+    if let Some(after) = after {
+        query = query.filter(purchases::purchase_id.gt(after));
+    }
+    if let Some(datapod_ids) = datapod_ids {
+        query = query.filter(purchases::datapod_id.eq_any(datapod_ids));
+    }
+    if let Some(buyer) = filter.buyer {
+        query = query.filter(purchases::buyer.eq(buyer));
+    }
+    if let Some(seller) = filter.seller {
+        query = query.filter(purchases::seller.eq(seller));
+    }
+    if let Some(status) = filter.status {
+        query = query.filter(purchases::status.eq(status));
+    }
+
+    let rows = query.load(&mut conn).await?;
+    Ok(connection(rows, limit, after.is_some(), |row| row.purchase_id.to_string()))
+}
+
+async fn escrow_connection(
+    ctx: &Context<'_>,
+    filter: EscrowFilter,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, EscrowView>> {
+    let state = ctx.data::<ApiState>()?;
+    let limit = state.limit(first.map(i64::from));
+    let after = id_cursor(after.as_deref())?;
+    let mut conn = state.connection().await?;
+
+    let mut query = escrows::table
+        .select(EscrowView::as_select())
+        .order(escrows::escrow_id)
+        .limit(limit + 1)
+        .into_boxed();
+
+    if let Some(after) = after {
+        query = query.filter(escrows::escrow_id.gt(after));
+    }
+    if let Some(buyer) = filter.buyer {
+        query = query.filter(escrows::buyer.eq(buyer));
+    }
+    if let Some(seller) = filter.seller {
+        query = query.filter(escrows::seller.eq(seller));
+    }
+    if let Some(status) = filter.status {
+        query = query.filter(escrows::status.eq(status));
+    }
+
+    let rows = query.load(&mut conn).await?;
+    Ok(connection(rows, limit, after.is_some(), |row| row.escrow_id.to_string()))
+}
+
+/// The ID a page of listings, purchases or escrows ends at, which they are ordered by
+fn id_cursor(after: Option<&str>) -> async_graphql::Result<Option<Address>> {
+    Ok(after.map(str::parse).transpose().map_err(|_| "Invalid cursor")?)
+}
+
+async fn event_connection(
+    ctx: &Context<'_>,
+    filter: EventFilter,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, DataPodEventView>> {
+    let state = ctx.data::<ApiState>()?;
+    let limit = state.limit(first.map(i64::from));
+    let cursor = match after.as_deref() {
+        Some(after) => Some(EventCursor::decode(after).map_err(|_| "Invalid cursor")?),
+        None => None,
+    };
+    let mut conn = state.connection().await?;
+    let rows = load_datapod_events(&mut conn, &filter, cursor, limit).await?;
+    Ok(connection(rows, limit, cursor.is_some(), |row| row.cursor().encode()))
+}
+
+async fn find_datapod(conn: &mut AsyncPgConnection, id: Address) -> Result<Option<DataPodView>> {
+    Ok(datapods::table
+        .find(id)
+        .select(DataPodView::as_select())
+        .first(conn)
+        .await
+        .optional()?)
+}
+
+async fn find_purchase(conn: &mut AsyncPgConnection, id: Address) -> Result<Option<PurchaseView>> {
+    Ok(purchases::table
+        .find(id)
+        .select(PurchaseView::as_select())
+        .first(conn)
+        .await
+        .optional()?)
+}
+
+async fn find_transaction(
+    conn: &mut AsyncPgConnection,
+    digest: Digest,
+) -> Result<Option<TransactionView>> {
+    Ok(transaction_digests::table
+        .filter(transaction_digests::tx_digest.eq(digest))
+        .select(TransactionView::as_select())
+        .first(conn)
+        .await
+        .optional()?)
+}
+
+/// IDs an object may be referred to by in events: its object ID, and the application-level ID
+/// stored in its `field`. Purchases refer to listings, and escrows to purchases, by the latter.
+async fn aliases(
+    ctx: &Context<'_>,
+    object_id: Address,
+    field: &str,
+) -> async_graphql::Result<Vec<String>> {
+    let loader = ctx.data::<DataLoader<DbLoader>>()?;
+    let data = loader.load_one(object_id).await?;
+
+    let mut ids = vec![object_id.to_string()];
+    if let Some(alias) = data.and_then(|data| data.get(field)?.as_str().map(str::to_string)) {
+        ids.push(alias);
+    }
+    Ok(ids)
+}
+
+/// Batches the lookups of related rows made while resolving a query, so that the items of a
+/// list cost one query per relationship rather than one each
+pub struct DbLoader(ApiState);
+
+/// A listing by `datapod_id`
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct DataPodKey(Address);
+
+/// A purchase by `purchase_id`
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PurchaseKey(Address);
+
+/// The escrow with the lowest `escrow_id` among those whose `purchase_id` is this
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct EscrowKey(String);
+
+/// The ID of the `module::name` object whose application-level ID in `field` is `value`
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct FieldKey {
+    module: &'static str,
+    name: &'static str,
+    field: &'static str,
+    value: String,
+}
+
+/// A failed batch fails every resolver waiting on it, so the error is shared
+type LoadError = Arc<anyhow::Error>;
+
+impl Loader<Digest> for DbLoader {
+    type Value = TransactionView;
+    type Error = LoadError;
+
+    async fn load(&self, keys: &[Digest]) -> Result<HashMap<Digest, Self::Value>, Self::Error> {
+        let mut conn = self.0.connection().await?;
+        let rows: Vec<TransactionView> = transaction_digests::table
+            .filter(transaction_digests::tx_digest.eq_any(keys.to_vec()))
+            .select(TransactionView::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(rows.into_iter().map(|row| (row.tx_digest, row)).collect())
+    }
+}
+
+impl Loader<DataPodKey> for DbLoader {
+    type Value = DataPodView;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[DataPodKey],
+    ) -> Result<HashMap<DataPodKey, Self::Value>, Self::Error> {
+        let ids: Vec<_> = keys.iter().map(|key| key.0).collect();
+        let mut conn = self.0.connection().await?;
+        let rows: Vec<DataPodView> = datapods::table
+            .filter(datapods::datapod_id.eq_any(ids))
+            .select(DataPodView::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(rows.into_iter().map(|row| (DataPodKey(row.datapod_id), row)).collect())
+    }
+}
+
+impl Loader<PurchaseKey> for DbLoader {
+    type Value = PurchaseView;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[PurchaseKey],
+    ) -> Result<HashMap<PurchaseKey, Self::Value>, Self::Error> {
+        let ids: Vec<_> = keys.iter().map(|key| key.0).collect();
+        let mut conn = self.0.connection().await?;
+        let rows: Vec<PurchaseView> = purchases::table
+            .filter(purchases::purchase_id.eq_any(ids))
+            .select(PurchaseView::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(rows.into_iter().map(|row| (PurchaseKey(row.purchase_id), row)).collect())
+    }
+}
+
+impl Loader<EscrowKey> for DbLoader {
+    type Value = EscrowView;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[EscrowKey],
+    ) -> Result<HashMap<EscrowKey, Self::Value>, Self::Error> {
+        let ids: Vec<_> = keys.iter().map(|key| key.0.clone()).collect();
+        let mut conn = self.0.connection().await?;
+        let rows: Vec<EscrowView> = escrows::table
+            .filter(escrows::purchase_id.eq_any(ids))
+            .select(EscrowView::as_select())
+            .order(escrows::escrow_id)
+            .load(&mut conn)
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        let mut escrows = HashMap::new();
+        for row in rows {
+            if let Some(purchase_id) = row.purchase_id.clone() {
+                escrows.entry(EscrowKey(purchase_id)).or_insert(row);
+            }
+        }
+        Ok(escrows)
+    }
+}
+
+/// `data` of objects in `smart_contract_objects`, by object ID
+impl Loader<Address> for DbLoader {
+    type Value = serde_json::Value;
+    type Error = LoadError;
+
+    async fn load(&self, keys: &[Address]) -> Result<HashMap<Address, Self::Value>, Self::Error> {
+        let mut conn = self.0.connection().await?;
+        let rows: Vec<(Address, Option<serde_json::Value>)> = smart_contract_objects::table
+            .filter(smart_contract_objects::object_id.eq_any(keys.to_vec()))
+            .select((smart_contract_objects::object_id, smart_contract_objects::data))
+            .load(&mut conn)
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(rows.into_iter().filter_map(|(id, data)| Some((id, data?))).collect())
+    }
+}
+
+impl Loader<FieldKey> for DbLoader {
+    type Value = Address;
+    type Error = LoadError;
+
+    async fn load(&self, keys: &[FieldKey]) -> Result<HashMap<FieldKey, Self::Value>, Self::Error> {
+        let mut groups: HashMap<_, Vec<String>> = HashMap::new();
+        for key in keys {
+            let group = groups.entry((key.module, key.name, key.field)).or_default();
+            group.push(key.value.clone());
+        }
+
+        let mut conn = self.0.connection().await?;
+        let mut objects = HashMap::new();
+        for ((module, name, field), values) in groups {
+            // `field` is one of our constants, and spelling it out in the SQL lets the lookup
+            // use the expression index on it
+            let value = sql::<Nullable<Text>>(&format!("data ->> '{field}'"));
+            let rows: Vec<(Address, Option<String>)> = smart_contract_objects::table
+                .filter(smart_contract_objects::object_type.like(format!("%::{module}::{name}")))
+                .filter(
+                    sql::<Bool>(&format!("data ->> '{field}' = ANY("))
+                        .bind::<Array<Text>, _>(values)
+                        .sql(")"),
+                )
+                .select((smart_contract_objects::object_id, value))
+                .load(&mut conn)
+                .await
+                .map_err(anyhow::Error::from)?;
+
+            for (object_id, value) in rows {
+                if let Some(value) = value {
+                    objects.insert(FieldKey { module, name, field, value }, object_id);
+                }
+            }
+        }
+        Ok(objects)
+    }
+}
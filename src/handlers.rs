@@ -1,22 +1,106 @@
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
-use anyhow::{Result};
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use diesel::ExpressionMethods;
-use diesel_async::RunQueryDsl;
+use bigdecimal::BigDecimal;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
 
 use sui_indexer_alt_framework::{
-    pipeline::sequential::Handler,
-    pipeline::Processor,
+    pipeline::{concurrent, sequential, Processor},
     postgres::{Connection, Db},
 };
+use move_core_types::language_storage::StructTag;
+use sui_types::base_types::ObjectID;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
 use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::object::{Data, Object};
+use sui_types::transaction::{Command, TransactionDataAPI, TransactionKind};
+use tracing::warn;
+use url::Url;
 
-use crate::models::{StoredDataPodEvent, StoredSmartContractObject, StoredTransactionDigest};
+use crate::cli::{PartitionedTable, Pipeline, TransactionFilter};
+use crate::events::{DataPodEvent, EscrowEvent, PurchaseEvent};
+use crate::objects;
+use crate::package::{
+    abort_error_name, SourceNetPackage, DATAPOD_MODULE, ESCROW_MODULE, PURCHASE_MODULE,
+};
+use crate::notify::{self, EventRef};
+use crate::outbox;
+use crate::partitions;
+use crate::models::{
+    bigint, rows_per_insert, Address, DataPodStatus, DeletionKind, Digest, EscrowStatus,
+    PurchaseStatus, StoredDataPod, StoredDataPodEvent, StoredEscrow, StoredEscrowEvent, StoredFailedTransaction,
+    StoredMoveCall, StoredObjectVersion, StoredPurchase, StoredPurchaseEvent,
+    StoredSmartContractObject, StoredTransactionDigest,
+};
 use crate::schema;
+use crate::schema::{datapod_events, escrow_events, purchase_events};
+use crate::webhooks;
+
+/// The events of `module` in `checkpoint` that `decode` recognises, each with the digest of its
+/// transaction, the transaction's index in the checkpoint and the event's index among all events
+/// of that transaction, which stays stable regardless of which events are kept. An event that does not decode (e.g. its layout changed
+/// in a package upgrade) is skipped rather than failing the checkpoint, which would stall the
+/// pipeline.
+fn decode_events<E>(
+    package: &SourceNetPackage,
+    checkpoint: &CheckpointData,
+    module: &str,
+    decode: fn(&str, &[u8]) -> Result<Option<E>>,
+) -> Result<Vec<(Digest, i64, i64, E)>> {
+    let mut events = Vec::new();
+
+    for (tx_idx, tx) in checkpoint.transactions.iter().enumerate() {
+        let tx_digest = Digest::from(*tx.transaction.digest());
+        let tx_index = bigint("transaction_index", tx_idx)?;
+
+        for (event_idx, event) in tx.events.iter().flat_map(|evs| evs.data.iter()).enumerate() {
+            if !package.emits(event, module) {
+                continue;
+            }
+
+            match decode(event.type_.name.as_str(), &event.contents) {
+                Ok(Some(decoded)) => {
+                    let event_index = bigint("event_index", event_idx)?;
+                    events.push((tx_digest, tx_index, event_index, decoded))
+                }
+                Ok(None) => {}
+                Err(err) => warn!(%tx_digest, event_idx, module, "Skipping event: {err:#}"),
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+/// Sequence number and timestamp of `checkpoint`, as stored in `BIGINT` columns
+fn checkpoint_position(checkpoint: &CheckpointData) -> Result<(i64, i64)> {
+    let summary = &checkpoint.checkpoint_summary;
+    Ok((
+        bigint("checkpoint_sequence_number", summary.sequence_number)?,
+        bigint("timestamp", summary.timestamp_ms)?,
+    ))
+}
 
 /// Handler for processing transaction digests from checkpoints
-pub struct TransactionDigestHandler;
+pub struct TransactionDigestHandler {
+    filter: Option<(SourceNetPackage, TransactionFilter)>,
+}
+
+impl TransactionDigestHandler {
+    /// Index every transaction on the network
+    pub fn all() -> Self {
+        Self { filter: None }
+    }
+
+    /// Index only transactions that `filter` relates to the SourceNet package
+    pub fn filtered(package: SourceNetPackage, filter: TransactionFilter) -> Self {
+        Self { filter: Some((package, filter)) }
+    }
+}
 
 #[async_trait]
 impl Processor for TransactionDigestHandler {
@@ -24,21 +108,40 @@ impl Processor for TransactionDigestHandler {
     type Value = StoredTransactionDigest;
 
     async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
-        let checkpoint_seq = checkpoint.checkpoint_summary.sequence_number as i64;
-        let digests = checkpoint
+        let (checkpoint_seq, timestamp_ms) = checkpoint_position(checkpoint)?;
+        checkpoint
             .transactions
             .iter()
-            .map(|tx| StoredTransactionDigest {
-                tx_digest: tx.transaction.digest().to_string(),
-                checkpoint_sequence_number: checkpoint_seq,
+            .enumerate()
+            .filter(|(_, tx)| {
+                self.filter
+                    .as_ref()
+                    .is_none_or(|(package, filter)| package.matches(tx, *filter))
             })
-            .collect();
-        Ok(digests)
+            .map(|(tx_idx, tx)| {
+                let data = tx.transaction.transaction_data();
+                let gas = tx.effects.gas_cost_summary();
+                let status = if tx.effects.status().is_ok() { "success" } else { "failure" };
+                Ok(StoredTransactionDigest {
+                    tx_digest: (*tx.transaction.digest()).into(),
+                    checkpoint_sequence_number: checkpoint_seq,
+                    sender: data.sender().into(),
+                    execution_status: status.to_string(),
+                    gas_budget: BigDecimal::from(data.gas_budget()),
+                    computation_cost: BigDecimal::from(gas.computation_cost),
+                    storage_cost: BigDecimal::from(gas.storage_cost),
+                    storage_rebate: BigDecimal::from(gas.storage_rebate),
+                    non_refundable_storage_fee: BigDecimal::from(gas.non_refundable_storage_fee),
+                    timestamp: timestamp_ms,
+                    transaction_index: bigint("transaction_index", tx_idx)?,
+                })
+            })
+            .collect()
     }
 }
 
 #[async_trait]
-impl Handler for TransactionDigestHandler {
+impl sequential::Handler for TransactionDigestHandler {
     type Store = Db;
     type Batch = Vec<Self::Value>;
 
@@ -49,21 +152,49 @@ impl Handler for TransactionDigestHandler {
     async fn commit<'a>(
         batch: &Self::Batch,
         conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        <Self as concurrent::Handler>::commit(batch, conn).await
+    }
+}
+
+/// Digests are written independently of each other, so batches may commit in any order
+#[async_trait]
+impl concurrent::Handler for TransactionDigestHandler {
+    type Store = Db;
+
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut Connection<'a>,
     ) -> Result<usize> {
         use schema::transaction_digests::dsl::*;
-        diesel::insert_into(transaction_digests)
-            .values(batch)
-            .on_conflict(tx_digest)
-            .do_nothing()
-            .execute(conn)
-            .await
-            .map_err(Into::into)
+        let checkpoints = values.iter().map(|tx| tx.checkpoint_sequence_number);
+        partitions::prepare(conn, PartitionedTable::TransactionDigests, checkpoints.clone()).await?;
+
+        let mut inserted = 0;
+        for chunk in values.chunks(rows_per_insert::<StoredTransactionDigest>()) {
+            inserted += diesel::insert_into(transaction_digests)
+                .values(chunk)
+                .on_conflict((tx_digest, checkpoint_sequence_number))
+                .do_nothing()
+                .execute(conn)
+                .await?;
+        }
+
+        notify::committed(conn, Pipeline::TransactionDigests, checkpoints, inserted, []).await?;
+        Ok(inserted)
     }
 }
 
-/// Handler for processing DataPod events from smart contracts
-#[allow(dead_code)]
-pub struct DataPodEventHandler;
+/// Handler for processing DataPod events and maintaining the current state of each listing
+pub struct DataPodEventHandler {
+    package: SourceNetPackage,
+}
+
+impl DataPodEventHandler {
+    pub fn new(package: SourceNetPackage) -> Self {
+        Self { package }
+    }
+}
 
 #[async_trait]
 impl Processor for DataPodEventHandler {
@@ -71,47 +202,470 @@ impl Processor for DataPodEventHandler {
     type Value = StoredDataPodEvent;
 
     async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
-        let checkpoint_seq = checkpoint.checkpoint_summary.sequence_number as i64;
-        let timestamp_ms = checkpoint.checkpoint_summary.timestamp_ms as i64;
-        let mut events = Vec::new();
+        let (checkpoint_seq, timestamp_ms) = checkpoint_position(checkpoint)?;
+        let events =
+            decode_events(&self.package, checkpoint, DATAPOD_MODULE, DataPodEvent::decode)?;
 
-        // Read smart contract address from environment
-        let _smart_contract_address = std::env::var("SMART_CONTRACT_ADDRESS")
-            .unwrap_or_else(|_| "0x0000000000000000000000000000000000000000000000000000000000000000".to_string());
+        Ok(events
+            .into_iter()
+            .map(|(tx_digest, tx_index, event_index, event)| {
+                stored_datapod_event(
+                    event,
+                    tx_digest,
+                    checkpoint_seq,
+                    tx_index,
+                    event_index,
+                    timestamp_ms,
+                )
+            })
+            .collect())
+    }
+}
 
-        for tx in checkpoint.transactions.iter() {
-            let tx_digest_str = tx.transaction.digest().to_string();
-
-            for (event_idx, event) in tx.events.iter().enumerate() {
-                // Event data is in the `data` field (BCS encoded Move struct)
-                // To extract specific fields, you need to deserialize based on your Move struct
-                // For now, we store all events with placeholder values
-                
-                let stored_event = StoredDataPodEvent {
-                    event_type: "datapod_event".to_string(),
-                    datapod_id: String::new(), // TODO: Extract from event.data by deserializing BCS
-                    seller: String::new(),     // TODO: Extract from event.data by deserializing BCS
-                    title: None,
-                    category: None,
-                    price_sui: None,
-                    kiosk_id: None,
-                    old_price: None,
-                    new_price: None,
-                    transaction_digest: tx_digest_str.clone(),
-                    checkpoint_sequence_number: checkpoint_seq,
-                    event_index: event_idx as i64,
-                    timestamp: timestamp_ms,
-                };
-                events.push(stored_event);
+/// Flatten a decoded DataPod event into a `datapod_events` row
+fn stored_datapod_event(
+    event: DataPodEvent,
+    transaction_digest: Digest,
+    checkpoint_sequence_number: i64,
+    transaction_index: i64,
+    event_index: i64,
+    timestamp: i64,
+) -> StoredDataPodEvent {
+    let (datapod_id, seller) = match &event {
+        DataPodEvent::Created(e) => (e.datapod_id, Some(e.seller)),
+        DataPodEvent::Published(e) => (e.datapod_id, Some(e.seller)),
+        DataPodEvent::Delisted(e) => (e.datapod_id, Some(e.seller)),
+        // The event carries no seller; it is left empty rather than guessed
+        DataPodEvent::PriceUpdated(e) => (e.datapod_id, None),
+    };
+
+    let mut stored = StoredDataPodEvent {
+        event_type: event.name().to_string(),
+        datapod_id: datapod_id.into(),
+        seller: seller.map(Address::from),
+        title: None,
+        category: None,
+        price_sui: None,
+        kiosk_id: None,
+        old_price: None,
+        new_price: None,
+        transaction_digest,
+        checkpoint_sequence_number,
+        transaction_index,
+        event_index,
+        timestamp,
+    };
+
+    match event {
+        DataPodEvent::Created(e) => {
+            stored.title = Some(e.title);
+            stored.category = Some(e.category);
+            stored.price_sui = Some(BigDecimal::from(e.price_sui));
+        }
+        DataPodEvent::Published(e) => {
+            stored.title = Some(e.title);
+            stored.category = Some(e.category);
+            stored.price_sui = Some(BigDecimal::from(e.price_sui));
+            stored.kiosk_id = Some(e.kiosk_id);
+        }
+        DataPodEvent::Delisted(_) => {}
+        DataPodEvent::PriceUpdated(e) => {
+            stored.old_price = Some(BigDecimal::from(e.old_price));
+            stored.new_price = Some(BigDecimal::from(e.new_price));
+        }
+    }
+
+    stored
+}
+
+#[async_trait]
+impl sequential::Handler for DataPodEventHandler {
+    type Store = Db;
+    type Batch = Vec<Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        batch.extend(values);
+    }
+
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        let inserted = <Self as concurrent::Handler>::commit(batch, conn).await?;
+        apply_datapod_events(conn, batch, Some(inserted)).await?;
+        Ok(inserted)
+    }
+}
+
+/// Only writes `datapod_events`. Everything that depends on event order (`datapods`,
+/// notifications, webhooks and the outbox) is left to [`DataPodStateHandler`].
+#[async_trait]
+impl concurrent::Handler for DataPodEventHandler {
+    type Store = Db;
+
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        let checkpoints = values.iter().map(|e| e.checkpoint_sequence_number);
+        partitions::prepare(conn, PartitionedTable::DatapodEvents, checkpoints).await?;
+
+        let mut inserted = 0;
+        for chunk in values.chunks(rows_per_insert::<StoredDataPodEvent>()) {
+            inserted += diesel::insert_into(datapod_events::table)
+                .values(chunk)
+                .on_conflict((
+                    datapod_events::transaction_digest,
+                    datapod_events::event_index,
+                    datapod_events::checkpoint_sequence_number,
+                ))
+                .do_nothing()
+                .execute(conn)
+                .await?;
+        }
+
+        Ok(inserted)
+    }
+}
+
+/// Queue webhooks, append to the outbox, update `datapods` and notify for `events`, in order.
+/// `rows` is the number of them newly written to `datapod_events`, or `None` in the state
+/// pipeline, which does not write them and notifies the number of `datapods` rows updated
+/// instead. Returns the number of `datapods` rows updated.
+async fn apply_datapod_events(
+    conn: &mut Connection<'_>,
+    events: &[StoredDataPodEvent],
+    rows: Option<usize>,
+) -> Result<usize> {
+    webhooks::enqueue(conn, events).await?;
+    outbox::append(conn, events).await?;
+
+    let states = fold_datapods(events);
+    let mut updated = 0;
+
+    // Listings only move forward: a row written by a later event is never rolled back
+    use schema::datapods::dsl::*;
+    for chunk in states.chunks(rows_per_insert::<StoredDataPod>()) {
+        updated += diesel::insert_into(datapods)
+            .values(chunk)
+            .on_conflict(datapod_id)
+            .do_update()
+            .set((
+                seller.eq(diesel::dsl::sql("COALESCE(excluded.seller, datapods.seller)")),
+                title.eq(diesel::dsl::sql("COALESCE(excluded.title, datapods.title)")),
+                category.eq(diesel::dsl::sql("COALESCE(excluded.category, datapods.category)")),
+                price_sui.eq(diesel::dsl::sql("COALESCE(excluded.price_sui, datapods.price_sui)")),
+                kiosk_id.eq(diesel::dsl::sql("COALESCE(excluded.kiosk_id, datapods.kiosk_id)")),
+                status.eq(diesel::dsl::sql("COALESCE(excluded.status, datapods.status)")),
+                created_checkpoint.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.created_checkpoint, datapods.created_checkpoint)",
+                )),
+                published_checkpoint.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.published_checkpoint, datapods.published_checkpoint)",
+                )),
+                last_updated_checkpoint.eq(diesel::dsl::sql("excluded.last_updated_checkpoint")),
+                last_event_index.eq(diesel::dsl::sql("excluded.last_event_index")),
+                last_updated_timestamp.eq(diesel::dsl::sql("excluded.last_updated_timestamp")),
+            ))
+            .filter(diesel::dsl::sql::<diesel::sql_types::Bool>(
+                "(excluded.last_updated_checkpoint, excluded.last_event_index) \
+                 > (datapods.last_updated_checkpoint, datapods.last_event_index)",
+            ))
+            .execute(conn)
+            .await?;
+    }
+
+    let checkpoints = events.iter().map(|e| e.checkpoint_sequence_number);
+    let refs = events.iter().map(|e| EventRef {
+        event_type: &e.event_type,
+        id: e.datapod_id.to_string().into(),
+    });
+    let rows = rows.unwrap_or(updated);
+    notify::committed(conn, Pipeline::DatapodEvents, checkpoints, rows, refs).await?;
+
+    Ok(updated)
+}
+
+/// Start the state pipeline `S` from where the event pipeline `E` has committed up to, unless `S`
+/// already has a watermark. Until `E` first runs concurrently it keeps the state table itself,
+/// which is then current up to its watermark. Without one, `S` would start from the first
+/// checkpoint, rebuild the table and notify every event again.
+pub async fn seed_state_watermark<E: Processor, S: Processor>(database_url: &Url) -> Result<()> {
+    let mut conn = AsyncPgConnection::establish(database_url.as_str())
+        .await
+        .context("Failed to connect to the database")?;
+
+    diesel::sql_query(
+        "INSERT INTO watermarks \
+         SELECT (jsonb_populate_record( \
+             NULL::watermarks, \
+             to_jsonb(w) || jsonb_build_object('pipeline', $2) \
+         )).* \
+         FROM watermarks w WHERE pipeline = $1 \
+         ON CONFLICT (pipeline) DO NOTHING",
+    )
+    .bind::<diesel::sql_types::Text, _>(E::NAME)
+    .bind::<diesel::sql_types::Text, _>(S::NAME)
+    .execute(&mut conn)
+    .await
+    .with_context(|| format!("Failed to seed the watermark of {}", S::NAME))?;
+
+    Ok(())
+}
+
+/// Hand the state table back to the event pipeline `E` when it runs sequentially again, after
+/// running concurrently with the state pipeline `S`. If `S` is behind, `E` resumes from its
+/// watermark, so that the events `S` has not applied yet are applied; the events, deliveries and
+/// outbox entries already written are left as they are. `S`'s watermark is removed, so it starts
+/// afresh from `E`'s the next time `E` runs concurrently.
+pub async fn rewind_to_state_watermark<E: Processor, S: Processor>(
+    database_url: &Url,
+) -> Result<()> {
+    let mut conn = AsyncPgConnection::establish(database_url.as_str())
+        .await
+        .context("Failed to connect to the database")?;
+
+    diesel::sql_query(
+        "WITH state AS (DELETE FROM watermarks WHERE pipeline = $2 RETURNING *) \
+         UPDATE watermarks w SET \
+             epoch_hi_inclusive = s.epoch_hi_inclusive, \
+             checkpoint_hi_inclusive = s.checkpoint_hi_inclusive, \
+             tx_hi = s.tx_hi, \
+             timestamp_ms_hi_inclusive = s.timestamp_ms_hi_inclusive \
+         FROM state s \
+         WHERE w.pipeline = $1 AND s.checkpoint_hi_inclusive < w.checkpoint_hi_inclusive",
+    )
+    .bind::<diesel::sql_types::Text, _>(E::NAME)
+    .bind::<diesel::sql_types::Text, _>(S::NAME)
+    .execute(&mut conn)
+    .await
+    .with_context(|| format!("Failed to rewind {} to the watermark of {}", E::NAME, S::NAME))?;
+
+    Ok(())
+}
+
+/// Sequential half of the DataPod events pipeline when `datapod_events` is written by a
+/// concurrent pipeline: applies events to `datapods` in order, without writing them
+pub struct DataPodStateHandler(pub DataPodEventHandler);
+
+#[async_trait]
+impl Processor for DataPodStateHandler {
+    const NAME: &'static str = "datapod_state_handler";
+    type Value = StoredDataPodEvent;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        self.0.process(checkpoint).await
+    }
+}
+
+#[async_trait]
+impl sequential::Handler for DataPodStateHandler {
+    type Store = Db;
+    type Batch = Vec<Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        batch.extend(values);
+    }
+
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        apply_datapod_events(conn, batch, None).await
+    }
+}
+
+/// Fold a batch of DataPod events into one state row per listing. The batch is already in
+/// `(checkpoint_sequence_number, event_index)` order, so later events overwrite earlier ones.
+fn fold_datapods(events: &[StoredDataPodEvent]) -> Vec<StoredDataPod> {
+    let mut datapods: BTreeMap<Address, StoredDataPod> = BTreeMap::new();
+
+    for event in events {
+        let datapod = datapods
+            .entry(event.datapod_id)
+            .or_insert_with(|| StoredDataPod {
+                datapod_id: event.datapod_id,
+                seller: None,
+                title: None,
+                category: None,
+                price_sui: None,
+                kiosk_id: None,
+                status: None,
+                created_checkpoint: None,
+                published_checkpoint: None,
+                last_updated_checkpoint: event.checkpoint_sequence_number,
+                last_event_index: event.event_index,
+                last_updated_timestamp: event.timestamp,
+            });
+
+        let status = match event.event_type.as_str() {
+            "DataPodCreated" => {
+                datapod.created_checkpoint = Some(event.checkpoint_sequence_number);
+                Some(DataPodStatus::Draft)
+            }
+            "DataPodPublished" => {
+                datapod.published_checkpoint = Some(event.checkpoint_sequence_number);
+                Some(DataPodStatus::Published)
             }
+            "DataPodDelisted" => Some(DataPodStatus::Delisted),
+            "DataPodPriceUpdated" => None,
+            _ => continue,
+        };
+
+        if let Some(status) = status {
+            datapod.status = Some(status.as_str().to_string());
         }
+        datapod.seller = event.seller.or(datapod.seller);
+        datapod.title = event.title.clone().or(datapod.title.take());
+        datapod.category = event.category.clone().or(datapod.category.take());
+        datapod.price_sui = event
+            .new_price
+            .clone()
+            .or_else(|| event.price_sui.clone())
+            .or(datapod.price_sui.take());
+        datapod.kiosk_id = event.kiosk_id.clone().or(datapod.kiosk_id.take());
+        datapod.last_updated_checkpoint = event.checkpoint_sequence_number;
+        datapod.last_event_index = event.event_index;
+        datapod.last_updated_timestamp = event.timestamp;
+    }
 
-        Ok(events)
+    datapods.into_values().collect()
+}
+
+/// Handler for processing Purchase events and maintaining the current state of each purchase
+pub struct PurchaseEventHandler {
+    package: SourceNetPackage,
+}
+
+impl PurchaseEventHandler {
+    pub fn new(package: SourceNetPackage) -> Self {
+        Self { package }
     }
 }
 
 #[async_trait]
-impl Handler for DataPodEventHandler {
+impl Processor for PurchaseEventHandler {
+    const NAME: &'static str = "purchase_event_handler";
+    type Value = StoredPurchaseEvent;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        let (checkpoint_seq, timestamp_ms) = checkpoint_position(checkpoint)?;
+        let events =
+            decode_events(&self.package, checkpoint, PURCHASE_MODULE, PurchaseEvent::decode)?;
+
+        Ok(events
+            .into_iter()
+            .map(|(tx_digest, _, event_index, event)| {
+                stored_purchase_event(event, tx_digest, checkpoint_seq, event_index, timestamp_ms)
+            })
+            .collect())
+    }
+}
+
+/// Flatten a decoded Purchase event into a `purchase_events` row
+fn stored_purchase_event(
+    event: PurchaseEvent,
+    transaction_digest: Digest,
+    checkpoint_sequence_number: i64,
+    event_index: i64,
+    timestamp: i64,
+) -> StoredPurchaseEvent {
+    let event_type = event.name().to_string();
+    let (purchase_id, datapod_id, buyer, seller, price_sui) = match event {
+        PurchaseEvent::Created(e) => (
+            e.purchase_id,
+            Some(e.datapod_id),
+            e.buyer,
+            Some(e.seller),
+            Some(BigDecimal::from(e.price_sui)),
+        ),
+        PurchaseEvent::Completed(e) => (
+            e.purchase_id,
+            None,
+            e.buyer,
+            Some(e.seller),
+            Some(BigDecimal::from(e.price_sui)),
+        ),
+        PurchaseEvent::Refunded(e) => {
+            (e.purchase_id, None, e.buyer, None, Some(BigDecimal::from(e.price_sui)))
+        }
+        PurchaseEvent::Disputed(e) => (e.purchase_id, None, e.buyer, Some(e.seller), None),
+    };
+
+    StoredPurchaseEvent {
+        event_type,
+        purchase_id: purchase_id.into(),
+        datapod_id,
+        buyer: buyer.into(),
+        seller: seller.map(Address::from),
+        price_sui,
+        transaction_digest,
+        checkpoint_sequence_number,
+        event_index,
+        timestamp,
+    }
+}
+
+/// Fold a batch of purchase events (in checkpoint order) into one state row per purchase
+fn fold_purchases(events: &[StoredPurchaseEvent]) -> Vec<StoredPurchase> {
+    let mut purchases: BTreeMap<Address, StoredPurchase> = BTreeMap::new();
+
+    for event in events {
+        let purchase = purchases
+            .entry(event.purchase_id)
+            .or_insert_with(|| StoredPurchase {
+                purchase_id: event.purchase_id,
+                datapod_id: None,
+                buyer: event.buyer,
+                seller: None,
+                price_sui: None,
+                status: PurchaseStatus::Pending.as_str().to_string(),
+                created_checkpoint: None,
+                created_timestamp: None,
+                completed_timestamp: None,
+                refunded_timestamp: None,
+                disputed_timestamp: None,
+                last_updated_checkpoint: event.checkpoint_sequence_number,
+                last_event_index: event.event_index,
+                last_updated_timestamp: event.timestamp,
+            });
+
+        let status = match event.event_type.as_str() {
+            "PurchaseCreated" => {
+                purchase.created_checkpoint = Some(event.checkpoint_sequence_number);
+                purchase.created_timestamp = Some(event.timestamp);
+                PurchaseStatus::Pending
+            }
+            "PurchaseCompleted" => {
+                purchase.completed_timestamp = Some(event.timestamp);
+                PurchaseStatus::Completed
+            }
+            "PurchaseRefunded" => {
+                purchase.refunded_timestamp = Some(event.timestamp);
+                PurchaseStatus::Refunded
+            }
+            "PurchaseDisputed" => {
+                purchase.disputed_timestamp = Some(event.timestamp);
+                PurchaseStatus::Disputed
+            }
+            _ => continue,
+        };
+
+        purchase.status = status.as_str().to_string();
+        purchase.buyer = event.buyer;
+        purchase.datapod_id = event.datapod_id.clone().or(purchase.datapod_id.take());
+        purchase.seller = event.seller.or(purchase.seller);
+        purchase.price_sui = event.price_sui.clone().or(purchase.price_sui.take());
+        purchase.last_updated_checkpoint = event.checkpoint_sequence_number;
+        purchase.last_event_index = event.event_index;
+        purchase.last_updated_timestamp = event.timestamp;
+    }
+
+    purchases.into_values().collect()
+}
+
+#[async_trait]
+impl sequential::Handler for PurchaseEventHandler {
     type Store = Db;
     type Batch = Vec<Self::Value>;
 
@@ -123,20 +677,518 @@ impl Handler for DataPodEventHandler {
         batch: &Self::Batch,
         conn: &mut Connection<'a>,
     ) -> Result<usize> {
-        use schema::datapod_events::dsl::*;
-        diesel::insert_into(datapod_events)
-            .values(batch)
-            .on_conflict((transaction_digest, event_index))
-            .do_nothing()
+        let inserted = <Self as concurrent::Handler>::commit(batch, conn).await?;
+        apply_purchase_events(conn, batch, Some(inserted)).await?;
+        Ok(inserted)
+    }
+}
+
+/// Only writes `purchase_events`. Everything that depends on event order (`purchases`,
+/// notifications, webhooks and the outbox) is left to [`PurchaseStateHandler`].
+#[async_trait]
+impl concurrent::Handler for PurchaseEventHandler {
+    type Store = Db;
+
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        let mut inserted = 0;
+        for chunk in values.chunks(rows_per_insert::<StoredPurchaseEvent>()) {
+            inserted += diesel::insert_into(purchase_events::table)
+                .values(chunk)
+                .on_conflict((purchase_events::transaction_digest, purchase_events::event_index))
+                .do_nothing()
+                .execute(conn)
+                .await?;
+        }
+
+        Ok(inserted)
+    }
+}
+
+/// Queue webhooks, append to the outbox, update `purchases` and notify for `events`, in order.
+/// `rows` is the number of them newly written to `purchase_events`, or `None` in the state
+/// pipeline, which does not write them and notifies the number of `purchases` rows updated
+/// instead. Returns the number of `purchases` rows updated.
+async fn apply_purchase_events(
+    conn: &mut Connection<'_>,
+    events: &[StoredPurchaseEvent],
+    rows: Option<usize>,
+) -> Result<usize> {
+    webhooks::enqueue(conn, events).await?;
+    outbox::append(conn, events).await?;
+
+    let states = fold_purchases(events);
+    let mut updated = 0;
+
+    // Columns the events did not observe are NULL in `excluded` and keep their stored value
+    use schema::purchases::dsl::*;
+    for chunk in states.chunks(rows_per_insert::<StoredPurchase>()) {
+        updated += diesel::insert_into(purchases)
+            .values(chunk)
+            .on_conflict(purchase_id)
+            .do_update()
+            .set((
+                datapod_id.eq(diesel::dsl::sql("COALESCE(excluded.datapod_id, purchases.datapod_id)")),
+                buyer.eq(diesel::dsl::sql("excluded.buyer")),
+                seller.eq(diesel::dsl::sql("COALESCE(excluded.seller, purchases.seller)")),
+                price_sui.eq(diesel::dsl::sql("COALESCE(excluded.price_sui, purchases.price_sui)")),
+                status.eq(diesel::dsl::sql("excluded.status")),
+                created_checkpoint.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.created_checkpoint, purchases.created_checkpoint)",
+                )),
+                created_timestamp.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.created_timestamp, purchases.created_timestamp)",
+                )),
+                completed_timestamp.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.completed_timestamp, purchases.completed_timestamp)",
+                )),
+                refunded_timestamp.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.refunded_timestamp, purchases.refunded_timestamp)",
+                )),
+                disputed_timestamp.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.disputed_timestamp, purchases.disputed_timestamp)",
+                )),
+                last_updated_checkpoint.eq(diesel::dsl::sql("excluded.last_updated_checkpoint")),
+                last_event_index.eq(diesel::dsl::sql("excluded.last_event_index")),
+                last_updated_timestamp.eq(diesel::dsl::sql("excluded.last_updated_timestamp")),
+            ))
+            .filter(diesel::dsl::sql::<diesel::sql_types::Bool>(
+                "(excluded.last_updated_checkpoint, excluded.last_event_index) \
+                 > (purchases.last_updated_checkpoint, purchases.last_event_index)",
+            ))
             .execute(conn)
-            .await
-            .map_err(Into::into)
+            .await?;
+    }
+
+    let checkpoints = events.iter().map(|e| e.checkpoint_sequence_number);
+    let refs = events.iter().map(|e| EventRef {
+        event_type: &e.event_type,
+        id: e.purchase_id.to_string().into(),
+    });
+    let rows = rows.unwrap_or(updated);
+    notify::committed(conn, Pipeline::PurchaseEvents, checkpoints, rows, refs).await?;
+
+    Ok(updated)
+}
+
+/// Sequential half of the Purchase events pipeline when `purchase_events` is written by a
+/// concurrent pipeline: applies events to `purchases` in order, without writing them
+pub struct PurchaseStateHandler(pub PurchaseEventHandler);
+
+#[async_trait]
+impl Processor for PurchaseStateHandler {
+    const NAME: &'static str = "purchase_state_handler";
+    type Value = StoredPurchaseEvent;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        self.0.process(checkpoint).await
+    }
+}
+
+#[async_trait]
+impl sequential::Handler for PurchaseStateHandler {
+    type Store = Db;
+    type Batch = Vec<Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        batch.extend(values);
+    }
+
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        apply_purchase_events(conn, batch, None).await
+    }
+}
+
+/// Handler for processing Escrow events and maintaining the current state of each escrow
+pub struct EscrowEventHandler {
+    package: SourceNetPackage,
+}
+
+impl EscrowEventHandler {
+    pub fn new(package: SourceNetPackage) -> Self {
+        Self { package }
+    }
+}
+
+#[async_trait]
+impl Processor for EscrowEventHandler {
+    const NAME: &'static str = "escrow_event_handler";
+    type Value = StoredEscrowEvent;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        let (checkpoint_seq, timestamp_ms) = checkpoint_position(checkpoint)?;
+        let events = decode_events(&self.package, checkpoint, ESCROW_MODULE, EscrowEvent::decode)?;
+
+        Ok(events
+            .into_iter()
+            .map(|(tx_digest, _, event_index, event)| {
+                stored_escrow_event(event, tx_digest, checkpoint_seq, event_index, timestamp_ms)
+            })
+            .collect())
+    }
+}
+
+/// Flatten a decoded Escrow event into an `escrow_events` row
+fn stored_escrow_event(
+    event: EscrowEvent,
+    transaction_digest: Digest,
+    checkpoint_sequence_number: i64,
+    event_index: i64,
+    timestamp: i64,
+) -> StoredEscrowEvent {
+    let event_type = event.name().to_string();
+    let (escrow_id, purchase_id, buyer, seller, amount) = match event {
+        EscrowEvent::Created(e) => (
+            e.escrow_id,
+            Some(e.purchase_id),
+            Some(e.buyer),
+            Some(e.seller),
+            e.amount,
+        ),
+        EscrowEvent::Released(e) => (e.escrow_id, None, None, Some(e.seller), e.amount),
+        EscrowEvent::Refunded(e) => (e.escrow_id, None, Some(e.buyer), None, e.amount),
+    };
+
+    StoredEscrowEvent {
+        event_type,
+        escrow_id: escrow_id.into(),
+        purchase_id,
+        buyer: buyer.map(Address::from),
+        seller: seller.map(Address::from),
+        amount: BigDecimal::from(amount),
+        transaction_digest,
+        checkpoint_sequence_number,
+        event_index,
+        timestamp,
+    }
+}
+
+/// Fold a batch of escrow events (in checkpoint order) into one state row per escrow
+fn fold_escrows(events: &[StoredEscrowEvent]) -> Vec<StoredEscrow> {
+    let mut escrows: BTreeMap<Address, StoredEscrow> = BTreeMap::new();
+
+    for event in events {
+        let escrow = escrows
+            .entry(event.escrow_id)
+            .or_insert_with(|| StoredEscrow {
+                escrow_id: event.escrow_id,
+                purchase_id: None,
+                buyer: None,
+                seller: None,
+                amount: event.amount.clone(),
+                status: EscrowStatus::Pending.as_str().to_string(),
+                created_checkpoint: None,
+                created_timestamp: None,
+                released_checkpoint: None,
+                released_timestamp: None,
+                refunded_checkpoint: None,
+                refunded_timestamp: None,
+                last_updated_checkpoint: event.checkpoint_sequence_number,
+                last_event_index: event.event_index,
+                last_updated_timestamp: event.timestamp,
+            });
+
+        let status = match event.event_type.as_str() {
+            "EscrowCreated" => {
+                escrow.created_checkpoint = Some(event.checkpoint_sequence_number);
+                escrow.created_timestamp = Some(event.timestamp);
+                EscrowStatus::Pending
+            }
+            "EscrowReleased" => {
+                escrow.released_checkpoint = Some(event.checkpoint_sequence_number);
+                escrow.released_timestamp = Some(event.timestamp);
+                EscrowStatus::Released
+            }
+            "EscrowRefunded" => {
+                escrow.refunded_checkpoint = Some(event.checkpoint_sequence_number);
+                escrow.refunded_timestamp = Some(event.timestamp);
+                EscrowStatus::Refunded
+            }
+            _ => continue,
+        };
+
+        // Release and refund name the payout recipient, which need not be the original party,
+        // so the parties recorded at creation take precedence
+        escrow.status = status.as_str().to_string();
+        escrow.purchase_id = escrow.purchase_id.take().or(event.purchase_id.clone());
+        escrow.buyer = escrow.buyer.or(event.buyer);
+        escrow.seller = escrow.seller.or(event.seller);
+        escrow.amount = event.amount.clone();
+        escrow.last_updated_checkpoint = event.checkpoint_sequence_number;
+        escrow.last_event_index = event.event_index;
+        escrow.last_updated_timestamp = event.timestamp;
+    }
+
+    escrows.into_values().collect()
+}
+
+#[async_trait]
+impl sequential::Handler for EscrowEventHandler {
+    type Store = Db;
+    type Batch = Vec<Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        batch.extend(values);
+    }
+
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        let inserted = <Self as concurrent::Handler>::commit(batch, conn).await?;
+        apply_escrow_events(conn, batch, Some(inserted)).await?;
+        Ok(inserted)
+    }
+}
+
+/// Only writes `escrow_events`. Everything that depends on event order (`escrows`,
+/// notifications, webhooks and the outbox) is left to [`EscrowStateHandler`].
+#[async_trait]
+impl concurrent::Handler for EscrowEventHandler {
+    type Store = Db;
+
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        let mut inserted = 0;
+        for chunk in values.chunks(rows_per_insert::<StoredEscrowEvent>()) {
+            inserted += diesel::insert_into(escrow_events::table)
+                .values(chunk)
+                .on_conflict((escrow_events::transaction_digest, escrow_events::event_index))
+                .do_nothing()
+                .execute(conn)
+                .await?;
+        }
+
+        Ok(inserted)
+    }
+}
+
+/// Queue webhooks, append to the outbox, update `escrows` and notify for `events`, in order.
+/// `rows` is the number of them newly written to `escrow_events`, or `None` in the state
+/// pipeline, which does not write them and notifies the number of `escrows` rows updated
+/// instead. Returns the number of `escrows` rows updated.
+async fn apply_escrow_events(
+    conn: &mut Connection<'_>,
+    events: &[StoredEscrowEvent],
+    rows: Option<usize>,
+) -> Result<usize> {
+    webhooks::enqueue(conn, events).await?;
+    outbox::append(conn, events).await?;
+
+    let states = fold_escrows(events);
+    let mut updated = 0;
+
+    // Parties seen at creation win over those named by a later release or refund
+    use schema::escrows::dsl::*;
+    for chunk in states.chunks(rows_per_insert::<StoredEscrow>()) {
+        updated += diesel::insert_into(escrows)
+            .values(chunk)
+            .on_conflict(escrow_id)
+            .do_update()
+            .set((
+                purchase_id.eq(diesel::dsl::sql("COALESCE(escrows.purchase_id, excluded.purchase_id)")),
+                buyer.eq(diesel::dsl::sql("COALESCE(escrows.buyer, excluded.buyer)")),
+                seller.eq(diesel::dsl::sql("COALESCE(escrows.seller, excluded.seller)")),
+                amount.eq(diesel::dsl::sql("excluded.amount")),
+                status.eq(diesel::dsl::sql("excluded.status")),
+                created_checkpoint.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.created_checkpoint, escrows.created_checkpoint)",
+                )),
+                created_timestamp.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.created_timestamp, escrows.created_timestamp)",
+                )),
+                released_checkpoint.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.released_checkpoint, escrows.released_checkpoint)",
+                )),
+                released_timestamp.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.released_timestamp, escrows.released_timestamp)",
+                )),
+                refunded_checkpoint.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.refunded_checkpoint, escrows.refunded_checkpoint)",
+                )),
+                refunded_timestamp.eq(diesel::dsl::sql(
+                    "COALESCE(excluded.refunded_timestamp, escrows.refunded_timestamp)",
+                )),
+                last_updated_checkpoint.eq(diesel::dsl::sql("excluded.last_updated_checkpoint")),
+                last_event_index.eq(diesel::dsl::sql("excluded.last_event_index")),
+                last_updated_timestamp.eq(diesel::dsl::sql("excluded.last_updated_timestamp")),
+            ))
+            .filter(diesel::dsl::sql::<diesel::sql_types::Bool>(
+                "(excluded.last_updated_checkpoint, excluded.last_event_index) \
+                 > (escrows.last_updated_checkpoint, escrows.last_event_index)",
+            ))
+            .execute(conn)
+            .await?;
+    }
+
+    let checkpoints = events.iter().map(|e| e.checkpoint_sequence_number);
+    let refs = events.iter().map(|e| EventRef {
+        event_type: &e.event_type,
+        id: e.escrow_id.to_string().into(),
+    });
+    let rows = rows.unwrap_or(updated);
+    notify::committed(conn, Pipeline::EscrowEvents, checkpoints, rows, refs).await?;
+
+    Ok(updated)
+}
+
+/// Sequential half of the Escrow events pipeline when `escrow_events` is written by a
+/// concurrent pipeline: applies events to `escrows` in order, without writing them
+pub struct EscrowStateHandler(pub EscrowEventHandler);
+
+#[async_trait]
+impl Processor for EscrowStateHandler {
+    const NAME: &'static str = "escrow_state_handler";
+    type Value = StoredEscrowEvent;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        self.0.process(checkpoint).await
+    }
+}
+
+#[async_trait]
+impl sequential::Handler for EscrowStateHandler {
+    type Store = Db;
+    type Batch = Vec<Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        batch.extend(values);
+    }
+
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        apply_escrow_events(conn, batch, None).await
     }
 }
 
 /// Handler for processing smart contract objects
-#[allow(dead_code)]
-pub struct SmartContractObjectHandler;
+pub struct SmartContractObjectHandler {
+    package: SourceNetPackage,
+}
+
+impl SmartContractObjectHandler {
+    pub fn new(package: SourceNetPackage) -> Self {
+        Self { package }
+    }
+}
+
+/// Whether `object` is a Move object of a type defined in `package`
+fn is_sourcenet_object(package: &SourceNetPackage, object: &Object) -> bool {
+    match &object.data {
+        Data::Move(move_object) => package.defines(&StructTag::from(move_object.type_().clone())),
+        Data::Package(_) => false,
+    }
+}
+
+/// Type, content type and JSON contents of a SourceNet object
+fn describe_object(
+    object: Option<&Object>,
+) -> Result<(String, Option<String>, Option<serde_json::Value>)> {
+    let Some(Data::Move(move_object)) = object.map(|o| &o.data) else {
+        return Ok((String::new(), None, None));
+    };
+
+    let tag = StructTag::from(move_object.type_().clone());
+    let data = objects::to_json(tag.module.as_str(), tag.name.as_str(), move_object.contents())?;
+    Ok((tag.to_canonical_string(true), Some("move_object".to_string()), data))
+}
+
+/// One row per SourceNet object created, mutated, deleted or wrapped in the checkpoint, in
+/// transaction order. Objects unwrapped and deleted in the same transaction have no state to
+/// tell their type from, so they are all included, with an empty `object_type`.
+fn object_rows(
+    package: &SourceNetPackage,
+    checkpoint: &CheckpointData,
+) -> Result<Vec<StoredSmartContractObject>> {
+    let (checkpoint_seq, _) = checkpoint_position(checkpoint)?;
+    let mut objects = Vec::new();
+
+    for tx in checkpoint.transactions.iter() {
+        let tx_digest = Digest::from(*tx.transaction.digest());
+        let input_objects: HashMap<ObjectID, &Object> =
+            tx.input_objects.iter().map(|o| (o.id(), o)).collect();
+        let output_objects: HashMap<ObjectID, &Object> =
+            tx.output_objects.iter().map(|o| (o.id(), o)).collect();
+
+        for (obj_ref, owner, _write_kind) in tx.effects.all_changed_objects() {
+            let output = output_objects.get(&obj_ref.0).copied();
+            if !output.is_some_and(|o| is_sourcenet_object(package, o)) {
+                continue;
+            }
+
+            let (object_type, content_type, data) = describe_object(output)?;
+
+            let stored_object = StoredSmartContractObject {
+                object_id: obj_ref.0.into(),
+                object_type,
+                owner: Some(owner.to_string()),
+                version: BigDecimal::from(obj_ref.1.value()),
+                digest: obj_ref.2.into(),
+                content_type,
+                data,
+                checkpoint_sequence_number: checkpoint_seq,
+                transaction_digest: tx_digest,
+                is_deleted: false,
+                deletion_kind: None,
+                deleted_at_checkpoint: None,
+                deleted_transaction_digest: None,
+            };
+            objects.push(stored_object);
+        }
+
+        // Deleted and wrapped objects are no longer live. Their last state comes from the
+        // transaction's inputs, except for objects that were unwrapped and deleted at once.
+        let removed = tx
+            .effects
+            .deleted()
+            .into_iter()
+            .chain(tx.effects.unwrapped_then_deleted())
+            .map(|obj_ref| (obj_ref, DeletionKind::Deleted))
+            .chain(
+                tx.effects
+                    .wrapped()
+                    .into_iter()
+                    .map(|obj_ref| (obj_ref, DeletionKind::Wrapped)),
+            );
+
+        for (obj_ref, kind) in removed {
+            let input = input_objects.get(&obj_ref.0).copied();
+            if input.is_some_and(|o| !is_sourcenet_object(package, o)) {
+                continue;
+            }
+
+            let (object_type, content_type, data) = describe_object(input)?;
+
+            let stored_object = StoredSmartContractObject {
+                object_id: obj_ref.0.into(),
+                object_type,
+                owner: input.map(|o| o.owner.to_string()),
+                version: BigDecimal::from(obj_ref.1.value()),
+                digest: obj_ref.2.into(),
+                content_type,
+                data,
+                checkpoint_sequence_number: checkpoint_seq,
+                transaction_digest: tx_digest,
+                is_deleted: true,
+                deletion_kind: Some(kind.as_str().to_string()),
+                deleted_at_checkpoint: Some(checkpoint_seq),
+                deleted_transaction_digest: Some(tx_digest),
+            };
+            objects.push(stored_object);
+        }
+    }
+
+    Ok(objects)
+}
 
 #[async_trait]
 impl Processor for SmartContractObjectHandler {
@@ -144,33 +1196,271 @@ impl Processor for SmartContractObjectHandler {
     type Value = StoredSmartContractObject;
 
     async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
-        let checkpoint_seq = checkpoint.checkpoint_summary.sequence_number as i64;
-        let mut objects = Vec::new();
+        object_rows(&self.package, checkpoint)
+    }
+}
+
+#[async_trait]
+impl sequential::Handler for SmartContractObjectHandler {
+    type Store = Db;
+    /// Latest version of each object seen in the batch, keyed by object ID. Postgres rejects an
+    /// upsert that touches the same row twice, so older versions are dropped while batching.
+    type Batch = BTreeMap<Address, Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        for value in values {
+            match batch.get(&value.object_id) {
+                Some(existing) if existing.version >= value.version => {}
+                _ => {
+                    batch.insert(value.object_id, value);
+                }
+            }
+        }
+    }
+
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        // Objects of unknown type were unwrapped and deleted at once, and only matter if they
+        // are already indexed
+        let (known, unknown): (Vec<_>, Vec<_>) =
+            batch.values().partition(|o| !o.object_type.is_empty());
+
+        use schema::smart_contract_objects::dsl::*;
+        let mut updated = 0;
+
+        // Only ever move an object forward, so replaying an older batch is a no-op. A deletion
+        // may not know the object's last state, in which case the stored state is kept.
+        for chunk in known.chunks(rows_per_insert::<StoredSmartContractObject>()) {
+            updated += diesel::insert_into(smart_contract_objects)
+                .values(chunk.to_vec())
+                .on_conflict(object_id)
+                .do_update()
+                .set((
+                    object_type.eq(diesel::dsl::sql(
+                        "COALESCE(NULLIF(excluded.object_type, ''), smart_contract_objects.object_type)",
+                    )),
+                    owner.eq(diesel::dsl::sql("COALESCE(excluded.owner, smart_contract_objects.owner)")),
+                    version.eq(diesel::dsl::sql("excluded.version")), // Use excluded to refer to the new value
+                    digest.eq(diesel::dsl::sql("excluded.digest")),
+                    content_type.eq(diesel::dsl::sql(
+                        "COALESCE(excluded.content_type, smart_contract_objects.content_type)",
+                    )),
+                    data.eq(diesel::dsl::sql(
+                        "CASE WHEN excluded.is_deleted \
+                         THEN COALESCE(excluded.data, smart_contract_objects.data) \
+                         ELSE excluded.data END",
+                    )),
+                    checkpoint_sequence_number.eq(diesel::dsl::sql("excluded.checkpoint_sequence_number")),
+                    transaction_digest.eq(diesel::dsl::sql("excluded.transaction_digest")),
+                    is_deleted.eq(diesel::dsl::sql("excluded.is_deleted")),
+                    deletion_kind.eq(diesel::dsl::sql("excluded.deletion_kind")),
+                    deleted_at_checkpoint.eq(diesel::dsl::sql("excluded.deleted_at_checkpoint")),
+                    deleted_transaction_digest.eq(diesel::dsl::sql("excluded.deleted_transaction_digest")),
+                ))
+                .filter(diesel::dsl::sql::<diesel::sql_types::Bool>(
+                    "excluded.version > smart_contract_objects.version",
+                ))
+                .execute(conn)
+                .await?;
+        }
+
+        for object in unknown {
+            updated += diesel::update(
+                smart_contract_objects
+                    .filter(object_id.eq(object.object_id))
+                    .filter(version.lt(&object.version)),
+            )
+            .set((
+                version.eq(&object.version),
+                digest.eq(object.digest),
+                checkpoint_sequence_number.eq(object.checkpoint_sequence_number),
+                transaction_digest.eq(object.transaction_digest),
+                is_deleted.eq(true),
+                deletion_kind.eq(&object.deletion_kind),
+                deleted_at_checkpoint.eq(object.deleted_at_checkpoint),
+                deleted_transaction_digest.eq(object.deleted_transaction_digest),
+            ))
+            .execute(conn)
+            .await?;
+        }
+
+        let checkpoints = batch.values().map(|o| o.checkpoint_sequence_number);
+        notify::committed(conn, Pipeline::SmartContractObjects, checkpoints, updated, []).await?;
+        Ok(updated)
+    }
+}
+
+/// Handler for recording every version of SourceNet objects
+pub struct ObjectVersionHandler {
+    package: SourceNetPackage,
+}
+
+impl ObjectVersionHandler {
+    pub fn new(package: SourceNetPackage) -> Self {
+        Self { package }
+    }
+}
+
+#[async_trait]
+impl Processor for ObjectVersionHandler {
+    const NAME: &'static str = "object_version_handler";
+    type Value = StoredObjectVersion;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        // A version that removed the object has no contents of its own
+        let versions = object_rows(&self.package, checkpoint)?
+            .into_iter()
+            .map(|object| {
+                let (owner, data) = if object.is_deleted {
+                    (None, None)
+                } else {
+                    (object.owner, object.data)
+                };
+
+                StoredObjectVersion {
+                    object_id: object.object_id,
+                    version: object.version,
+                    digest: object.digest,
+                    object_type: object.object_type,
+                    owner,
+                    data,
+                    deletion_kind: object.deletion_kind,
+                    transaction_digest: object.transaction_digest,
+                    checkpoint_sequence_number: object.checkpoint_sequence_number,
+                }
+            })
+            .collect();
+
+        Ok(versions)
+    }
+}
+
+#[async_trait]
+impl sequential::Handler for ObjectVersionHandler {
+    type Store = Db;
+    type Batch = Vec<Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        batch.extend(values);
+    }
+
+    /// Not a concurrent pipeline: objects of unknown type were unwrapped and deleted at once, and
+    /// are only recorded for objects with earlier versions, whose type they take. The checkpoint
+    /// has no other record of the type, so the earlier versions must be committed first.
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        use schema::object_versions::dsl::*;
+        let checkpoints = batch.iter().map(|o| o.checkpoint_sequence_number);
+        partitions::prepare(conn, PartitionedTable::ObjectVersions, checkpoints.clone()).await?;
+
+        let (known, unknown): (Vec<_>, Vec<_>) =
+            batch.iter().partition(|o| !o.object_type.is_empty());
+
+        let mut inserted = 0;
+        for chunk in known.chunks(rows_per_insert::<StoredObjectVersion>()) {
+            inserted += diesel::insert_into(object_versions)
+                .values(chunk.to_vec())
+                .on_conflict((object_id, version, checkpoint_sequence_number))
+                .do_nothing()
+                .execute(conn)
+                .await?;
+        }
+
+        for object in unknown {
+            inserted += diesel::sql_query(
+                "INSERT INTO object_versions \
+                     (object_id, version, digest, object_type, deletion_kind, \
+                      transaction_digest, checkpoint_sequence_number) \
+                 SELECT $1, $2, $3, object_type, $4, $5, $6 FROM object_versions \
+                 WHERE object_id = $1 AND version < $2 \
+                 ORDER BY version DESC LIMIT 1 \
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind::<diesel::sql_types::Bytea, _>(object.object_id)
+            .bind::<diesel::sql_types::Numeric, _>(&object.version)
+            .bind::<diesel::sql_types::Bytea, _>(object.digest)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&object.deletion_kind)
+            .bind::<diesel::sql_types::Bytea, _>(object.transaction_digest)
+            .bind::<diesel::sql_types::BigInt, _>(object.checkpoint_sequence_number)
+            .execute(conn)
+            .await?;
+        }
+
+        notify::committed(conn, Pipeline::ObjectVersions, checkpoints, inserted, []).await?;
+        Ok(inserted)
+    }
+}
+
+/// Handler for recording the Move calls made by SourceNet transactions
+pub struct MoveCallHandler {
+    package: SourceNetPackage,
+}
+
+impl MoveCallHandler {
+    pub fn new(package: SourceNetPackage) -> Self {
+        Self { package }
+    }
+}
+
+#[async_trait]
+impl Processor for MoveCallHandler {
+    const NAME: &'static str = "move_call_handler";
+    type Value = StoredMoveCall;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        let (checkpoint_seq, timestamp_ms) = checkpoint_position(checkpoint)?;
+        let mut calls = Vec::new();
 
         for tx in checkpoint.transactions.iter() {
-            let tx_digest_str = tx.transaction.digest().to_string();
-
-            for (obj_ref, owner, _write_kind) in tx.effects.all_changed_objects() {
-                let stored_object = StoredSmartContractObject {
-                    object_id: obj_ref.0.to_string(),
-                    object_type: String::new(),
-                    owner: Some(owner.to_string()),
-                    version: obj_ref.1.value() as i64,
-                    digest: obj_ref.2.to_string(),
-                    content_type: None,
-                    checkpoint_sequence_number: checkpoint_seq,
-                    transaction_digest: tx_digest_str.clone(),
+            // Every call of a SourceNet transaction is kept, so calls to upgraded versions of
+            // the package and the framework calls around them are recorded too
+            if !self.package.matches(tx, TransactionFilter::SourceNet) {
+                continue;
+            }
+
+            let data = tx.transaction.transaction_data();
+            let TransactionKind::ProgrammableTransaction(ptb) = data.kind() else {
+                continue;
+            };
+
+            let tx_digest = Digest::from(*tx.transaction.digest());
+            let sender = Address::from(data.sender());
+
+            for (command_idx, command) in ptb.commands.iter().enumerate() {
+                let Command::MoveCall(call) = command else {
+                    continue;
                 };
-                objects.push(stored_object);
+
+                let type_arguments = call
+                    .type_arguments
+                    .iter()
+                    .map(|ty| serde_json::Value::String(ty.to_canonical_string(true)))
+                    .collect();
+
+                calls.push(StoredMoveCall {
+                    transaction_digest: tx_digest,
+                    command_index: bigint("command_index", command_idx)?,
+                    package: call.package.into(),
+                    module: call.module.to_string(),
+                    function: call.function.to_string(),
+                    type_arguments: serde_json::Value::Array(type_arguments),
+                    sender,
+                    checkpoint_sequence_number: checkpoint_seq,
+                    timestamp: timestamp_ms,
+                });
             }
         }
 
-        Ok(objects)
+        Ok(calls)
     }
 }
 
 #[async_trait]
-impl Handler for SmartContractObjectHandler {
+impl sequential::Handler for MoveCallHandler {
     type Store = Db;
     type Batch = Vec<Self::Value>;
 
@@ -182,17 +1472,347 @@ impl Handler for SmartContractObjectHandler {
         batch: &Self::Batch,
         conn: &mut Connection<'a>,
     ) -> Result<usize> {
-        use schema::smart_contract_objects::dsl::*;
-        diesel::insert_into(smart_contract_objects)
-            .values(batch)
-            .on_conflict(object_id)
-            .do_update()
-            .set((
-                version.eq(diesel::dsl::sql("excluded.version")), // Use excluded to refer to the new value
-                digest.eq(diesel::dsl::sql("excluded.digest")),
-            ))
-            .execute(conn)
-            .await
-            .map_err(Into::into)
+        <Self as concurrent::Handler>::commit(batch, conn).await
+    }
+}
+
+/// Calls are keyed by `(transaction_digest, command_index)`, so commit order does not matter
+#[async_trait]
+impl concurrent::Handler for MoveCallHandler {
+    type Store = Db;
+
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        use schema::move_calls::dsl::*;
+        let mut inserted = 0;
+        for chunk in values.chunks(rows_per_insert::<StoredMoveCall>()) {
+            inserted += diesel::insert_into(move_calls)
+                .values(chunk)
+                .on_conflict((transaction_digest, command_index))
+                .do_nothing()
+                .execute(conn)
+                .await?;
+        }
+
+        let checkpoints = values.iter().map(|c| c.checkpoint_sequence_number);
+        notify::committed(conn, Pipeline::MoveCalls, checkpoints, inserted, []).await?;
+        Ok(inserted)
+    }
+}
+
+/// Handler for recording failed SourceNet transactions and the Move abort that failed them
+pub struct FailedTransactionHandler {
+    package: SourceNetPackage,
+}
+
+impl FailedTransactionHandler {
+    pub fn new(package: SourceNetPackage) -> Self {
+        Self { package }
+    }
+}
+
+#[async_trait]
+impl Processor for FailedTransactionHandler {
+    const NAME: &'static str = "failed_transaction_handler";
+    type Value = StoredFailedTransaction;
+
+    async fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        let (checkpoint_seq, timestamp_ms) = checkpoint_position(checkpoint)?;
+        let mut failures = Vec::new();
+
+        for tx in checkpoint.transactions.iter() {
+            let ExecutionStatus::Failure { error, command } = tx.effects.status() else {
+                continue;
+            };
+
+            let abort = match error {
+                ExecutionFailureStatus::MoveAbort(location, code) => Some((location, *code)),
+                _ => None,
+            };
+
+            // A failed transaction emits no events, so it is attributed to SourceNet by its
+            // calls, its objects, or an abort raised inside the package
+            let sourcenet_abort =
+                abort.is_some_and(|(location, _)| self.package.is(*location.module.address()));
+            if !sourcenet_abort && !self.package.matches(tx, TransactionFilter::SourceNet) {
+                continue;
+            }
+
+            let (abort_module, abort_function, abort_code, abort_error_name) = match abort {
+                Some((location, code)) => {
+                    let module = location.module.name().as_str();
+                    let error_name = if sourcenet_abort {
+                        abort_error_name(module, code).map(str::to_string)
+                    } else {
+                        None
+                    };
+                    (
+                        Some(format!("{}::{}", location.module.address().to_hex_literal(), module)),
+                        location.function_name.clone(),
+                        Some(BigDecimal::from(code)),
+                        error_name,
+                    )
+                }
+                None => (None, None, None, None),
+            };
+
+            failures.push(StoredFailedTransaction {
+                transaction_digest: (*tx.transaction.digest()).into(),
+                sender: tx.transaction.transaction_data().sender().into(),
+                error: error.to_string(),
+                failed_command_index: command
+                    .map(|idx| bigint("failed_command_index", idx))
+                    .transpose()?,
+                abort_module,
+                abort_function,
+                abort_code,
+                abort_error_name,
+                checkpoint_sequence_number: checkpoint_seq,
+                timestamp: timestamp_ms,
+            });
+        }
+
+        Ok(failures)
+    }
+}
+
+#[async_trait]
+impl sequential::Handler for FailedTransactionHandler {
+    type Store = Db;
+    type Batch = Vec<Self::Value>;
+
+    fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
+        batch.extend(values);
+    }
+
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        <Self as concurrent::Handler>::commit(batch, conn).await
+    }
+}
+
+/// Failures are keyed by transaction digest, so commit order does not matter
+#[async_trait]
+impl concurrent::Handler for FailedTransactionHandler {
+    type Store = Db;
+
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        use schema::failed_transactions::dsl::*;
+        let mut inserted = 0;
+        for chunk in values.chunks(rows_per_insert::<StoredFailedTransaction>()) {
+            inserted += diesel::insert_into(failed_transactions)
+                .values(chunk)
+                .on_conflict(transaction_digest)
+                .do_nothing()
+                .execute(conn)
+                .await?;
+        }
+
+        let checkpoints = values.iter().map(|tx| tx.checkpoint_sequence_number);
+        notify::committed(conn, Pipeline::FailedTransactions, checkpoints, inserted, []).await?;
+        Ok(inserted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(s: &str) -> Address {
+        s.parse().unwrap()
+    }
+
+    fn digest() -> Digest {
+        "11111111111111111111111111111111".parse().unwrap()
+    }
+
+    fn datapod_event(event_type: &str, id: &str, checkpoint: i64) -> StoredDataPodEvent {
+        StoredDataPodEvent {
+            event_type: event_type.to_string(),
+            datapod_id: address(id),
+            seller: None,
+            title: None,
+            category: None,
+            price_sui: None,
+            kiosk_id: None,
+            old_price: None,
+            new_price: None,
+            transaction_digest: digest(),
+            checkpoint_sequence_number: checkpoint,
+            transaction_index: 0,
+            event_index: 0,
+            timestamp: checkpoint * 1000,
+        }
+    }
+
+    fn purchase_event(event_type: &str, id: &str, checkpoint: i64) -> StoredPurchaseEvent {
+        StoredPurchaseEvent {
+            event_type: event_type.to_string(),
+            purchase_id: address(id),
+            datapod_id: None,
+            buyer: address("0xb"),
+            seller: None,
+            price_sui: None,
+            transaction_digest: digest(),
+            checkpoint_sequence_number: checkpoint,
+            event_index: 0,
+            timestamp: checkpoint * 1000,
+        }
+    }
+
+    fn escrow_event(event_type: &str, id: &str, checkpoint: i64) -> StoredEscrowEvent {
+        StoredEscrowEvent {
+            event_type: event_type.to_string(),
+            escrow_id: address(id),
+            purchase_id: None,
+            buyer: None,
+            seller: None,
+            amount: BigDecimal::from(100),
+            transaction_digest: digest(),
+            checkpoint_sequence_number: checkpoint,
+            event_index: 0,
+            timestamp: checkpoint * 1000,
+        }
+    }
+
+    #[test]
+    fn datapod_events_fold_into_the_latest_state() {
+        let created = StoredDataPodEvent {
+            seller: Some(address("0x5")),
+            title: Some("Weather".to_string()),
+            category: Some("climate".to_string()),
+            price_sui: Some(BigDecimal::from(10)),
+            ..datapod_event("DataPodCreated", "0x1", 1)
+        };
+        let price_updated = StoredDataPodEvent {
+            old_price: Some(BigDecimal::from(10)),
+            new_price: Some(BigDecimal::from(12)),
+            ..datapod_event("DataPodPriceUpdated", "0x1", 2)
+        };
+        let published = StoredDataPodEvent {
+            seller: Some(address("0x5")),
+            kiosk_id: Some("0xk".to_string()),
+            ..datapod_event("DataPodPublished", "0x1", 3)
+        };
+        let unknown = datapod_event("DataPodRenamed", "0x1", 4);
+        let other = StoredDataPodEvent {
+            seller: Some(address("0x6")),
+            ..datapod_event("DataPodCreated", "0x2", 2)
+        };
+
+        let datapods = fold_datapods(&[created, other, price_updated, published, unknown]);
+        assert_eq!(datapods.len(), 2);
+
+        let datapod = &datapods[0];
+        assert_eq!(datapod.datapod_id, address("0x1"));
+        assert_eq!(datapod.seller, Some(address("0x5")));
+        assert_eq!(datapod.title.as_deref(), Some("Weather"));
+        assert_eq!(datapod.category.as_deref(), Some("climate"));
+        assert_eq!(datapod.price_sui, Some(BigDecimal::from(12)));
+        assert_eq!(datapod.kiosk_id.as_deref(), Some("0xk"));
+        assert_eq!(datapod.status.as_deref(), Some("published"));
+        assert_eq!(datapod.created_checkpoint, Some(1));
+        assert_eq!(datapod.published_checkpoint, Some(3));
+        assert_eq!(datapod.last_updated_checkpoint, 3);
+        assert_eq!(datapod.last_updated_timestamp, 3000);
+
+        let datapod = &datapods[1];
+        assert_eq!(datapod.datapod_id, address("0x2"));
+        assert_eq!(datapod.seller, Some(address("0x6")));
+        assert_eq!(datapod.status.as_deref(), Some("draft"));
+    }
+
+    #[test]
+    fn price_update_leaves_the_status_alone() {
+        let event = StoredDataPodEvent {
+            new_price: Some(BigDecimal::from(7)),
+            ..datapod_event("DataPodPriceUpdated", "0x1", 5)
+        };
+
+        let datapods = fold_datapods(&[event]);
+        assert_eq!(datapods.len(), 1);
+        assert_eq!(datapods[0].status, None);
+        assert_eq!(datapods[0].seller, None);
+        assert_eq!(datapods[0].price_sui, Some(BigDecimal::from(7)));
+    }
+
+    #[test]
+    fn purchase_events_fold_into_the_latest_state() {
+        let created = StoredPurchaseEvent {
+            datapod_id: Some("0xd".to_string()),
+            seller: Some(address("0x5")),
+            price_sui: Some(BigDecimal::from(10)),
+            ..purchase_event("PurchaseCreated", "0x1", 1)
+        };
+        let completed = purchase_event("PurchaseCompleted", "0x1", 4);
+        let disputed = purchase_event("PurchaseDisputed", "0x2", 2);
+
+        let purchases = fold_purchases(&[created, disputed, completed]);
+        assert_eq!(purchases.len(), 2);
+
+        let purchase = &purchases[0];
+        assert_eq!(purchase.purchase_id, address("0x1"));
+        assert_eq!(purchase.status, "completed");
+        assert_eq!(purchase.buyer, address("0xb"));
+        assert_eq!(purchase.seller, Some(address("0x5")));
+        assert_eq!(purchase.datapod_id.as_deref(), Some("0xd"));
+        assert_eq!(purchase.price_sui, Some(BigDecimal::from(10)));
+        assert_eq!(purchase.created_checkpoint, Some(1));
+        assert_eq!(purchase.created_timestamp, Some(1000));
+        assert_eq!(purchase.completed_timestamp, Some(4000));
+        assert_eq!(purchase.refunded_timestamp, None);
+        assert_eq!(purchase.last_updated_checkpoint, 4);
+
+        let purchase = &purchases[1];
+        assert_eq!(purchase.status, "disputed");
+        assert_eq!(purchase.created_checkpoint, None);
+        assert_eq!(purchase.disputed_timestamp, Some(2000));
+    }
+
+    #[test]
+    fn escrow_payouts_keep_the_original_parties() {
+        let created = StoredEscrowEvent {
+            purchase_id: Some("0xp".to_string()),
+            buyer: Some(address("0xb")),
+            seller: Some(address("0x5")),
+            ..escrow_event("EscrowCreated", "0x1", 1)
+        };
+        let released = StoredEscrowEvent {
+            seller: Some(address("0x9")),
+            amount: BigDecimal::from(0),
+            ..escrow_event("EscrowReleased", "0x1", 3)
+        };
+        let refunded = StoredEscrowEvent {
+            buyer: Some(address("0xc")),
+            ..escrow_event("EscrowRefunded", "0x2", 2)
+        };
+
+        let escrows = fold_escrows(&[created, refunded, released]);
+        assert_eq!(escrows.len(), 2);
+
+        let escrow = &escrows[0];
+        assert_eq!(escrow.escrow_id, address("0x1"));
+        assert_eq!(escrow.status, "released");
+        assert_eq!(escrow.purchase_id.as_deref(), Some("0xp"));
+        assert_eq!(escrow.buyer, Some(address("0xb")));
+        assert_eq!(escrow.seller, Some(address("0x5")));
+        assert_eq!(escrow.amount, BigDecimal::from(0));
+        assert_eq!(escrow.created_checkpoint, Some(1));
+        assert_eq!(escrow.released_checkpoint, Some(3));
+        assert_eq!(escrow.released_timestamp, Some(3000));
+        assert_eq!(escrow.last_updated_checkpoint, 3);
+
+        // An escrow first seen at its refund records the recipient as its buyer
+        let escrow = &escrows[1];
+        assert_eq!(escrow.status, "refunded");
+        assert_eq!(escrow.buyer, Some(address("0xc")));
+        assert_eq!(escrow.refunded_timestamp, Some(2000));
     }
 }
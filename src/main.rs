@@ -1,15 +1,31 @@
+mod api;
+mod cli;
+mod events;
+mod graphql;
 mod models;
 mod handlers;
+mod notify;
+mod objects;
+mod outbox;
+mod package;
+mod partitions;
+mod pruner;
 mod schema;
+mod webhooks;
 
-use handlers::TransactionDigestHandler;
-use anyhow::Result;
+use cli::{Cli, Command, Pipeline, TransactionFilter};
+use handlers::{
+    rewind_to_state_watermark, seed_state_watermark, DataPodEventHandler, DataPodStateHandler,
+    EscrowEventHandler, EscrowStateHandler, FailedTransactionHandler, MoveCallHandler,
+    ObjectVersionHandler, PurchaseEventHandler, PurchaseStateHandler, SmartContractObjectHandler,
+    TransactionDigestHandler,
+};
+use package::SourceNetPackage;
+use std::time::Duration;
+use anyhow::{ensure, Context, Result};
 use clap::Parser;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
-use sui_indexer_alt_framework::{
-    cluster::{Args, IndexerCluster},
-    pipeline::sequential::SequentialConfig,
-};
+use sui_indexer_alt_framework::cluster::IndexerCluster;
 use url::Url;
 
 // Embed database migrations into the binary so they run automatically on startup
@@ -26,24 +42,157 @@ async fn main() -> Result<()> {
         .parse::<Url>()
         .expect("Invalid database URL");
 
-    // Parse command-line arguments (checkpoint range, URLs, performance settings)
-    let args = Args::parse();
+    // Parse command-line arguments (checkpoint range, URLs, performance settings, pipelines)
+    let cli = Cli::parse();
+    match &cli.command {
+        Some(Command::Serve(args)) => return api::serve(database_url, args.clone()).await,
+        Some(Command::Webhooks(command)) => {
+            return webhooks::run(database_url, command.clone()).await;
+        }
+        Some(Command::Outbox(command)) => {
+            return outbox::run(database_url, command.clone()).await;
+        }
+        Some(Command::Partitions(command)) => {
+            return partitions::run(database_url, command.clone()).await;
+        }
+        None => {}
+    }
+
+    let pipelines = cli
+        .enabled_pipelines()
+        .into_iter()
+        .map(|pipeline| {
+            let concurrent = cli.concurrent_config(pipeline)?;
+            Ok((pipeline, cli.sequential_config(pipeline), concurrent))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    ensure!(
+        cli.concurrent.iter().all(|pipeline| pipeline.can_run_concurrently()),
+        "--concurrent does not apply to smart-contract-objects or object-versions",
+    );
+
+    // SourceNet package whose events are indexed, parsed once so a bad address fails fast.
+    // Only required when a pipeline filters on it, so unfiltered digest-only instances can omit it.
+    let package = if pipelines.iter().any(|(pipeline, ..)| cli.requires_package(*pipeline)) {
+        Some(SourceNetPackage::from_env()?)
+    } else {
+        None
+    };
+    let package =
+        || package.clone().context("SMART_CONTRACT_ADDRESS must be set in the environment");
+
+    notify::enable(cli.notifying_pipelines());
+    partitions::configure(cli.partition_size);
+
+    let pruner_database_url = database_url.clone();
+    let state_database_url = database_url.clone();
 
     // Build and configure the indexer cluster
     let mut cluster = IndexerCluster::builder()
-        .with_args(args)                    // Apply command-line configuration
+        .with_args(cli.indexer)             // Apply command-line configuration
         .with_database_url(database_url)    // Set up database URL
         .with_migrations(&MIGRATIONS)       // Enable automatic schema migrations
         .build()
         .await?;
 
-    // Register our custom sequential pipeline with the cluster
-    cluster
-        .sequential_pipeline(
-            TransactionDigestHandler,           // Our processor/handler implementation
-            SequentialConfig::default(),        // Use default batch sizes and checkpoint lag
-        )
-        .await?;
+    // Register `$handler` as a concurrent pipeline when `$concurrent` is set, else as a
+    // sequential one
+    macro_rules! register {
+        ($handler:expr, $config:expr, $concurrent:expr) => {
+            match $concurrent {
+                Some(concurrent) => cluster.concurrent_pipeline($handler, concurrent).await?,
+                None => cluster.sequential_pipeline($handler, $config).await?,
+            }
+        };
+    }
+
+    // Register each selected pipeline with the cluster. When an event pipeline runs
+    // concurrently, the current state it keeps is applied in event order by a sequential
+    // pipeline of its own, with the same configuration. That pipeline starts where the event
+    // pipeline had got to, which kept the state itself while it ran sequentially, and when the
+    // event pipeline runs sequentially again it resumes from wherever the state had got to.
+    for (pipeline, config, concurrent) in pipelines {
+        match pipeline {
+            Pipeline::TransactionDigests => {
+                let handler = match cli.transaction_filter {
+                    TransactionFilter::All => TransactionDigestHandler::all(),
+                    filter => TransactionDigestHandler::filtered(package()?, filter),
+                };
+                register!(handler, config, concurrent)
+            }
+            Pipeline::DatapodEvents => {
+                if concurrent.is_some() {
+                    seed_state_watermark::<DataPodEventHandler, DataPodStateHandler>(
+                        &state_database_url,
+                    )
+                    .await?;
+                    let state = DataPodStateHandler(DataPodEventHandler::new(package()?));
+                    cluster.sequential_pipeline(state, config.clone()).await?;
+                } else {
+                    rewind_to_state_watermark::<DataPodEventHandler, DataPodStateHandler>(
+                        &state_database_url,
+                    )
+                    .await?;
+                }
+                register!(DataPodEventHandler::new(package()?), config, concurrent)
+            }
+            Pipeline::PurchaseEvents => {
+                if concurrent.is_some() {
+                    seed_state_watermark::<PurchaseEventHandler, PurchaseStateHandler>(
+                        &state_database_url,
+                    )
+                    .await?;
+                    let state = PurchaseStateHandler(PurchaseEventHandler::new(package()?));
+                    cluster.sequential_pipeline(state, config.clone()).await?;
+                } else {
+                    rewind_to_state_watermark::<PurchaseEventHandler, PurchaseStateHandler>(
+                        &state_database_url,
+                    )
+                    .await?;
+                }
+                register!(PurchaseEventHandler::new(package()?), config, concurrent)
+            }
+            Pipeline::EscrowEvents => {
+                if concurrent.is_some() {
+                    seed_state_watermark::<EscrowEventHandler, EscrowStateHandler>(
+                        &state_database_url,
+                    )
+                    .await?;
+                    let state = EscrowStateHandler(EscrowEventHandler::new(package()?));
+                    cluster.sequential_pipeline(state, config.clone()).await?;
+                } else {
+                    rewind_to_state_watermark::<EscrowEventHandler, EscrowStateHandler>(
+                        &state_database_url,
+                    )
+                    .await?;
+                }
+                register!(EscrowEventHandler::new(package()?), config, concurrent)
+            }
+            Pipeline::SmartContractObjects => {
+                cluster.sequential_pipeline(SmartContractObjectHandler::new(package()?), config).await?
+            }
+            Pipeline::ObjectVersions => {
+                cluster.sequential_pipeline(ObjectVersionHandler::new(package()?), config).await?
+            }
+            Pipeline::MoveCalls => {
+                register!(MoveCallHandler::new(package()?), config, concurrent)
+            }
+            Pipeline::FailedTransactions => {
+                register!(FailedTransactionHandler::new(package()?), config, concurrent)
+            }
+        }
+    }
+
+    // Delete rows past their retention in the background, once migrations have run
+    if !cli.retention.is_empty() {
+        tokio::spawn(pruner::run(
+            pruner_database_url,
+            cli.retention,
+            Duration::from_millis(cli.prune_interval_ms),
+            cli.prune_batch_size,
+        ));
+    }
 
     // Start the indexer and wait for completion
     let handle = cluster.run().await?;
@@ -1,44 +1,655 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use bigdecimal::BigDecimal;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
 use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Binary;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sui_indexer_alt_framework::FieldCount;
-use crate::schema::{datapod_events, smart_contract_objects, transaction_digests};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::{ObjectDigest, TransactionDigest};
+use crate::package::{DATAPOD_MODULE, ESCROW_MODULE, PURCHASE_MODULE};
+use crate::schema::{
+    datapod_events, datapods, escrow_events, escrows, failed_transactions, move_calls,
+    object_versions, outbox, purchase_events, purchases, smart_contract_objects,
+    transaction_digests, webhook_deliveries, webhook_subscriptions,
+};
 
 /// Represents a DataPod event from the smart contract
-#[derive(Insertable, Debug, Clone, FieldCount)]
+#[derive(Insertable, Serialize, Debug, Clone, FieldCount)]
 #[diesel(table_name = datapod_events)]
 pub struct StoredDataPodEvent {
     pub event_type: String,
-    pub datapod_id: String,
-    pub seller: String,
+    pub datapod_id: Address,
+    /// `None` for price updates, which do not carry the seller
+    pub seller: Option<Address>,
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub price_sui: Option<BigDecimal>,
+    pub kiosk_id: Option<String>,
+    pub old_price: Option<BigDecimal>,
+    pub new_price: Option<BigDecimal>,
+    pub transaction_digest: Digest,
+    pub checkpoint_sequence_number: i64,
+    /// Position of the transaction in the checkpoint, which orders events of different
+    /// transactions in the same checkpoint
+    pub transaction_index: i64,
+    pub event_index: i64,
+    pub timestamp: i64,
+}
+
+/// Listing status of a DataPod, mirroring `DataPod.status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataPodStatus {
+    Draft,
+    Published,
+    Delisted,
+}
+
+impl DataPodStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Published => "published",
+            Self::Delisted => "delisted",
+        }
+    }
+}
+
+/// Represents the current state of a DataPod listing. `None` fields are left untouched
+/// when the row is upserted over an existing one.
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = datapods)]
+pub struct StoredDataPod {
+    pub datapod_id: Address,
+    pub seller: Option<Address>,
     pub title: Option<String>,
     pub category: Option<String>,
-    pub price_sui: Option<i64>,
+    pub price_sui: Option<BigDecimal>,
     pub kiosk_id: Option<String>,
-    pub old_price: Option<i64>,
-    pub new_price: Option<i64>,
-    pub transaction_digest: String,
+    pub status: Option<String>,
+    pub created_checkpoint: Option<i64>,
+    pub published_checkpoint: Option<i64>,
+    pub last_updated_checkpoint: i64,
+    pub last_event_index: i64,
+    pub last_updated_timestamp: i64,
+}
+
+/// Represents a Purchase event from the smart contract
+#[derive(Insertable, Serialize, Debug, Clone, FieldCount)]
+#[diesel(table_name = purchase_events)]
+pub struct StoredPurchaseEvent {
+    pub event_type: String,
+    pub purchase_id: Address,
+    /// The DataPod id as the contract passed it, which is a string rather than an `ID`
+    pub datapod_id: Option<String>,
+    pub buyer: Address,
+    pub seller: Option<Address>,
+    pub price_sui: Option<BigDecimal>,
+    pub transaction_digest: Digest,
     pub checkpoint_sequence_number: i64,
     pub event_index: i64,
     pub timestamp: i64,
 }
 
+/// Lifecycle status of a purchase, mirroring `PurchaseRequest.status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurchaseStatus {
+    Pending,
+    Completed,
+    Refunded,
+    Disputed,
+}
+
+impl PurchaseStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Completed => "completed",
+            Self::Refunded => "refunded",
+            Self::Disputed => "disputed",
+        }
+    }
+}
+
+/// Represents the current state of a purchase. `None` fields are left untouched
+/// when the row is upserted over an existing one.
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = purchases)]
+pub struct StoredPurchase {
+    pub purchase_id: Address,
+    pub datapod_id: Option<String>,
+    pub buyer: Address,
+    pub seller: Option<Address>,
+    pub price_sui: Option<BigDecimal>,
+    pub status: String,
+    pub created_checkpoint: Option<i64>,
+    pub created_timestamp: Option<i64>,
+    pub completed_timestamp: Option<i64>,
+    pub refunded_timestamp: Option<i64>,
+    pub disputed_timestamp: Option<i64>,
+    pub last_updated_checkpoint: i64,
+    pub last_event_index: i64,
+    pub last_updated_timestamp: i64,
+}
+
+/// Represents an Escrow event from the smart contract
+#[derive(Insertable, Serialize, Debug, Clone, FieldCount)]
+#[diesel(table_name = escrow_events)]
+pub struct StoredEscrowEvent {
+    pub event_type: String,
+    pub escrow_id: Address,
+    /// The purchase id as the contract passed it, which is a string rather than an `ID`
+    pub purchase_id: Option<String>,
+    pub buyer: Option<Address>,
+    pub seller: Option<Address>,
+    pub amount: BigDecimal,
+    pub transaction_digest: Digest,
+    pub checkpoint_sequence_number: i64,
+    pub event_index: i64,
+    pub timestamp: i64,
+}
+
+/// Status of an escrow, mirroring `Escrow.status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowStatus {
+    Pending,
+    Released,
+    Refunded,
+}
+
+impl EscrowStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Released => "released",
+            Self::Refunded => "refunded",
+        }
+    }
+}
+
+/// Represents the current state of an escrow. `None` fields are left untouched
+/// when the row is upserted over an existing one.
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = escrows)]
+pub struct StoredEscrow {
+    pub escrow_id: Address,
+    pub purchase_id: Option<String>,
+    pub buyer: Option<Address>,
+    pub seller: Option<Address>,
+    pub amount: BigDecimal,
+    pub status: String,
+    pub created_checkpoint: Option<i64>,
+    pub created_timestamp: Option<i64>,
+    pub released_checkpoint: Option<i64>,
+    pub released_timestamp: Option<i64>,
+    pub refunded_checkpoint: Option<i64>,
+    pub refunded_timestamp: Option<i64>,
+    pub last_updated_checkpoint: i64,
+    pub last_event_index: i64,
+    pub last_updated_timestamp: i64,
+}
+
 /// Represents a smart contract object stored on-chain
 #[derive(Insertable, Debug, Clone, FieldCount)]
 #[diesel(table_name = smart_contract_objects)]
 pub struct StoredSmartContractObject {
-    pub object_id: String,
+    pub object_id: Address,
     pub object_type: String,
     pub owner: Option<String>,
-    pub version: i64,
-    pub digest: String,
+    pub version: BigDecimal,
+    pub digest: Digest,
     pub content_type: Option<String>,
+    pub data: Option<serde_json::Value>,
+    pub checkpoint_sequence_number: i64,
+    pub transaction_digest: Digest,
+    pub is_deleted: bool,
+    pub deletion_kind: Option<String>,
+    pub deleted_at_checkpoint: Option<i64>,
+    pub deleted_transaction_digest: Option<Digest>,
+}
+
+/// Represents one version of a SourceNet object
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = object_versions)]
+pub struct StoredObjectVersion {
+    pub object_id: Address,
+    pub version: BigDecimal,
+    pub digest: Digest,
+    pub object_type: String,
+    pub owner: Option<String>,
+    pub data: Option<serde_json::Value>,
+    pub deletion_kind: Option<String>,
+    pub transaction_digest: Digest,
     pub checkpoint_sequence_number: i64,
-    pub transaction_digest: String,
+}
+
+/// Why an object stopped being live
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeletionKind {
+    Deleted,
+    Wrapped,
+}
+
+impl DeletionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deleted => "deleted",
+            Self::Wrapped => "wrapped",
+        }
+    }
 }
 
 /// Represents a transaction digest for indexing
 #[derive(Insertable, Debug, Clone, FieldCount)]
 #[diesel(table_name = transaction_digests)]
 pub struct StoredTransactionDigest {
-    pub tx_digest: String,
+    pub tx_digest: Digest,
+    pub checkpoint_sequence_number: i64,
+    pub sender: Address,
+    pub execution_status: String,
+    pub gas_budget: BigDecimal,
+    pub computation_cost: BigDecimal,
+    pub storage_cost: BigDecimal,
+    pub storage_rebate: BigDecimal,
+    pub non_refundable_storage_fee: BigDecimal,
+    pub timestamp: i64,
+    pub transaction_index: i64,
+}
+
+/// Represents a Move call command of a SourceNet transaction
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = move_calls)]
+pub struct StoredMoveCall {
+    pub transaction_digest: Digest,
+    pub command_index: i64,
+    pub package: Address,
+    pub module: String,
+    pub function: String,
+    pub type_arguments: serde_json::Value,
+    pub sender: Address,
     pub checkpoint_sequence_number: i64,
+    pub timestamp: i64,
+}
+
+/// Represents a failed SourceNet transaction
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = failed_transactions)]
+pub struct StoredFailedTransaction {
+    pub transaction_digest: Digest,
+    pub sender: Address,
+    pub error: String,
+    pub failed_command_index: Option<i64>,
+    pub abort_module: Option<String>,
+    pub abort_function: Option<String>,
+    pub abort_code: Option<BigDecimal>,
+    pub abort_error_name: Option<String>,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp: i64,
+}
+
+/// A webhook subscription. `None` filters match every event.
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = webhook_subscriptions)]
+pub struct StoredWebhookSubscription {
+    pub url: String,
+    pub secret: String,
+    pub event_type: Option<String>,
+    pub seller: Option<String>,
+    pub datapod_id: Option<String>,
+}
+
+/// A pending delivery of one event to one webhook subscription
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct StoredWebhookDelivery {
+    pub subscription_id: i64,
+    pub event_type: String,
+    pub transaction_digest: Digest,
+    pub event_index: i64,
+    pub checkpoint_sequence_number: i64,
+    pub payload: serde_json::Value,
+}
+
+/// A decoded SourceNet event queued for downstream consumers
+#[derive(Insertable, Debug, Clone, FieldCount)]
+#[diesel(table_name = outbox)]
+pub struct StoredOutboxEvent {
+    pub module: String,
+    pub event_type: String,
+    pub entity_id: Address,
+    pub transaction_digest: Digest,
+    pub event_index: i64,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp: i64,
+    pub payload: serde_json::Value,
+}
+
+/// The fields shared by events of every SourceNet module, used to route them to consumers
+pub struct EventDescription<'a> {
+    /// Module that emitted the event
+    pub module: &'static str,
+    pub event_type: &'a str,
+    /// The DataPod, purchase or escrow the event is about
+    pub entity_id: Address,
+    pub seller: Option<Address>,
+    /// The DataPod as the event refers to it, which purchases do by an application-level ID
+    pub datapod_id: Option<Cow<'a, str>>,
+    pub transaction_digest: Digest,
+    pub event_index: i64,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp: i64,
+}
+
+/// A stored event of any SourceNet module
+pub trait StoredEvent: Serialize {
+    fn describe(&self) -> EventDescription<'_>;
+}
+
+impl StoredEvent for StoredDataPodEvent {
+    fn describe(&self) -> EventDescription<'_> {
+        EventDescription {
+            module: DATAPOD_MODULE,
+            event_type: &self.event_type,
+            entity_id: self.datapod_id,
+            seller: self.seller,
+            datapod_id: Some(self.datapod_id.to_string().into()),
+            transaction_digest: self.transaction_digest,
+            event_index: self.event_index,
+            checkpoint_sequence_number: self.checkpoint_sequence_number,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+impl StoredEvent for StoredPurchaseEvent {
+    fn describe(&self) -> EventDescription<'_> {
+        EventDescription {
+            module: PURCHASE_MODULE,
+            event_type: &self.event_type,
+            entity_id: self.purchase_id,
+            seller: self.seller,
+            datapod_id: self.datapod_id.as_deref().map(Cow::Borrowed),
+            transaction_digest: self.transaction_digest,
+            event_index: self.event_index,
+            checkpoint_sequence_number: self.checkpoint_sequence_number,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+impl StoredEvent for StoredEscrowEvent {
+    fn describe(&self) -> EventDescription<'_> {
+        EventDescription {
+            module: ESCROW_MODULE,
+            event_type: &self.event_type,
+            entity_id: self.escrow_id,
+            seller: self.seller,
+            datapod_id: None,
+            transaction_digest: self.transaction_digest,
+            event_index: self.event_index,
+            checkpoint_sequence_number: self.checkpoint_sequence_number,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// A value that does not fit the `BIGINT` column it is stored in. Move `u64` amounts are stored
+/// as `NUMERIC(20,0)` and never raise this; it guards checkpoint numbers, timestamps and indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub field: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {} does not fit in a BIGINT column", self.field, self.value)
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
+/// Rows of `T` that fit in one `INSERT` under Postgres's limit of 65,535 bind parameters
+pub fn rows_per_insert<T: FieldCount>() -> usize {
+    u16::MAX as usize / T::FIELD_COUNT
+}
+
+/// Convert `value` of `field` for a `BIGINT` column, failing instead of wrapping around
+pub fn bigint<T>(field: &'static str, value: T) -> Result<i64, OutOfRange>
+where
+    T: TryInto<i64> + fmt::Display + Copy,
+{
+    value.try_into().map_err(|_| OutOfRange {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// A Sui address or object ID. Stored as 32 bytes in a `BYTEA` column and written as
+/// `0x`-prefixed hex everywhere else; `sui_address_text` renders it the same way in SQL.
+#[derive(AsExpression, FromSqlRow, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[diesel(sql_type = Binary)]
+pub struct Address([u8; 32]);
+
+/// A transaction or object digest. Stored as 32 bytes in a `BYTEA` column and written in Base58
+/// everywhere else; `sui_digest_text` renders it the same way in SQL.
+#[derive(AsExpression, FromSqlRow, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[diesel(sql_type = Binary)]
+pub struct Digest([u8; 32]);
+
+impl From<SuiAddress> for Address {
+    fn from(address: SuiAddress) -> Self {
+        Self(address.to_inner())
+    }
+}
+
+impl From<ObjectID> for Address {
+    fn from(id: ObjectID) -> Self {
+        Self(id.into_bytes())
+    }
+}
+
+impl From<TransactionDigest> for Digest {
+    fn from(digest: TransactionDigest) -> Self {
+        Self(digest.into_inner())
+    }
+}
+
+impl From<ObjectDigest> for Digest {
+    fn from(digest: ObjectDigest) -> Self {
+        Self(digest.into_inner())
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Transaction and object digests share the Base58 encoding
+        fmt::Display::fmt(&TransactionDigest::new(self.0), f)
+    }
+}
+
+impl FromStr for Address {
+    type Err = anyhow::Error;
+
+    /// Accepts hex in either case, with or without the `0x` prefix and leading zeros
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix("0x").unwrap_or(s);
+        let id = ObjectID::from_hex_literal(&format!("0x{hex}"))
+            .map_err(|err| anyhow!("Invalid address {s:?}: {err}"))?;
+        Ok(id.into())
+    }
+}
+
+impl FromStr for Digest {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digest = TransactionDigest::from_str(s)
+            .map_err(|err| anyhow!("Invalid digest {s:?}: {err}"))?;
+        Ok(digest.into())
+    }
+}
+
+/// Column, serde and string conversions shared by the 32-byte identifiers
+macro_rules! bytes32 {
+    ($ty:ident) => {
+        impl ToSql<Binary, Pg> for $ty {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                <[u8] as ToSql<Binary, Pg>>::to_sql(self.0.as_slice(), out)
+            }
+        }
+
+        impl FromSql<Binary, Pg> for $ty {
+            fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                let bytes = <Vec<u8> as FromSql<Binary, Pg>>::from_sql(bytes)?;
+                let bytes = <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+                    format!("Expected 32 bytes for {}, got {}", stringify!($ty), bytes.len())
+                })?;
+                Ok(Self(bytes))
+            }
+        }
+
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = Cow::<str>::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+bytes32!(Address);
+bytes32!(Digest);
+
+#[cfg(test)]
+mod tests {
+    use diesel::dsl::sql;
+    use diesel::sql_types::Text;
+    use diesel_migrations::MigrationHarness;
+
+    use super::*;
+
+    /// Digests in hex and Base58, which writes each leading zero byte as a '1'
+    const DIGESTS: &[(&str, &str)] = &[
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "11111111111111111111111111111111",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "1thX6LZfHDZZKUs92febYZhYRcXddmzfzF2NvTkPNE",
+        ),
+        (
+            "00000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+            "11CiMQsCUhqABwwLyCFeX2iPnBZX3s28dUUCBrirhs",
+        ),
+    ];
+
+    fn digests() -> impl Iterator<Item = (Digest, &'static str)> {
+        DIGESTS.iter().map(|(bytes, base58)| {
+            let bytes = hex::decode(bytes).unwrap().try_into().unwrap();
+            (Digest(bytes), *base58)
+        })
+    }
+
+    fn address(last: u8) -> Address {
+        let mut bytes = [0; 32];
+        bytes[31] = last;
+        Address(bytes)
+    }
+
+    #[test]
+    fn digests_round_trip_through_base58() {
+        for (digest, base58) in digests() {
+            assert_eq!(digest.to_string(), base58);
+            assert_eq!(base58.parse::<Digest>().unwrap(), digest);
+            assert_eq!(serde_json::to_value(digest).unwrap(), base58);
+        }
+
+        // '0', 'O', 'I' and 'l' are not in the alphabet
+        assert!("0OIl".parse::<Digest>().is_err());
+    }
+
+    #[test]
+    fn addresses_round_trip_through_hex() {
+        let full = Address([0xab; 32]);
+        assert_eq!(full.to_string(), format!("0x{}", "ab".repeat(32)));
+        assert_eq!(full.to_string().parse::<Address>().unwrap(), full);
+        assert_eq!(format!("0x{}", "AB".repeat(32)).parse::<Address>().unwrap(), full);
+        assert_eq!(serde_json::to_value(full).unwrap(), full.to_string());
+
+        let short = format!("0x{}2", "0".repeat(63));
+        assert_eq!(address(2).to_string(), short);
+        for written in ["0x2", "2", short.as_str()] {
+            assert_eq!(written.parse::<Address>().unwrap(), address(2));
+        }
+        assert!("0xg".parse::<Address>().is_err());
+    }
+
+    /// Checks `sui_digest`, `sui_digest_text`, `sui_address` and `sui_address_text` against the
+    /// Rust conversions. Needs a scratch database in `TEST_DATABASE_URL`.
+    #[test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    fn sql_functions_agree_with_rust() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL is not set");
+        let mut conn = PgConnection::establish(&url).unwrap();
+        conn.run_pending_migrations(crate::MIGRATIONS).unwrap();
+
+        for (digest, base58) in digests() {
+            let decoded: Digest = sql::<Binary>("SELECT sui_digest(")
+                .bind::<Text, _>(base58)
+                .sql(")")
+                .get_result(&mut conn)
+                .unwrap();
+            assert_eq!(decoded, digest);
+
+            let encoded: String = sql::<Text>("SELECT sui_digest_text(")
+                .bind::<Binary, _>(digest)
+                .sql(")")
+                .get_result(&mut conn)
+                .unwrap();
+            assert_eq!(encoded, base58);
+        }
+
+        let full = format!("0x{}", "AB".repeat(32));
+        for written in ["0x2", "2", full.as_str()] {
+            let address = written.parse::<Address>().unwrap();
+            let decoded: Address = sql::<Binary>("SELECT sui_address(")
+                .bind::<Text, _>(written)
+                .sql(")")
+                .get_result(&mut conn)
+                .unwrap();
+            assert_eq!(decoded, address);
+
+            let encoded: String = sql::<Text>("SELECT sui_address_text(")
+                .bind::<Binary, _>(address)
+                .sql(")")
+                .get_result(&mut conn)
+                .unwrap();
+            assert_eq!(encoded, address.to_string());
+        }
+    }
 }
@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use diesel::sql_types::Text;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use sui_indexer_alt_framework::postgres::Connection;
+
+use crate::cli::Pipeline;
+
+/// Postgres rejects `NOTIFY` payloads of 8000 bytes or more
+const MAX_PAYLOAD_BYTES: usize = 7900;
+
+/// Pipelines that notify when they commit. `Handler::commit` has no access to the handler,
+/// so this is process-wide and set once at startup.
+static ENABLED: OnceLock<Vec<Pipeline>> = OnceLock::new();
+
+pub fn enable(pipelines: Vec<Pipeline>) {
+    let _ = ENABLED.set(pipelines);
+}
+
+/// Channel `pipeline` notifies on: `sourcenet_` followed by the pipeline name in snake case
+pub fn channel(pipeline: Pipeline) -> String {
+    format!("sourcenet_{}", name(pipeline).replace('-', "_"))
+}
+
+fn name(pipeline: Pipeline) -> String {
+    pipeline
+        .to_possible_value()
+        .expect("pipelines are never skipped")
+        .get_name()
+        .to_string()
+}
+
+/// An event committed by an event pipeline
+#[derive(Serialize, Debug)]
+pub struct EventRef<'a> {
+    #[serde(rename = "type")]
+    pub event_type: &'a str,
+    pub id: Cow<'a, str>,
+}
+
+#[derive(Serialize, Debug)]
+struct Payload<'a> {
+    pipeline: String,
+    first_checkpoint: i64,
+    last_checkpoint: i64,
+    rows: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    events: Vec<EventRef<'a>>,
+    /// Set when `events` was cut short to fit the payload limit
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+}
+
+/// Notify listeners of `pipeline` that a batch covering `checkpoints` and writing `rows` rows
+/// was committed. The notification is sent in the commit's transaction, so it is delivered only
+/// if the batch lands. Does nothing if `pipeline` does not notify or the batch is empty.
+pub async fn committed<'e>(
+    conn: &mut Connection<'_>,
+    pipeline: Pipeline,
+    checkpoints: impl IntoIterator<Item = i64>,
+    rows: usize,
+    events: impl IntoIterator<Item = EventRef<'e>>,
+) -> Result<()> {
+    if !ENABLED.get().is_some_and(|enabled| enabled.contains(&pipeline)) {
+        return Ok(());
+    }
+
+    let mut checkpoints = checkpoints.into_iter();
+    let Some(first) = checkpoints.next() else {
+        return Ok(());
+    };
+    let (first_checkpoint, last_checkpoint) =
+        checkpoints.fold((first, first), |(lo, hi), cp| (lo.min(cp), hi.max(cp)));
+
+    let mut payload = Payload {
+        pipeline: name(pipeline),
+        first_checkpoint,
+        last_checkpoint,
+        rows,
+        events: Vec::new(),
+        truncated: false,
+    };
+
+    // Leave room for the `events` and `truncated` keys, then add events while they fit
+    let mut size =
+        serde_json::to_string(&payload)?.len() + r#","events":[],"truncated":true"#.len();
+    for event in events {
+        let event_size = serde_json::to_string(&event)?.len() + 1;
+        if size + event_size > MAX_PAYLOAD_BYTES {
+            payload.truncated = true;
+            break;
+        }
+        size += event_size;
+        payload.events.push(event);
+    }
+
+    diesel::sql_query("SELECT pg_notify($1, $2)")
+        .bind::<Text, _>(channel(pipeline))
+        .bind::<Text, _>(serde_json::to_string(&payload)?)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::package::{DATAPOD_MODULE, ESCROW_MODULE, PURCHASE_MODULE};
+
+/// Mirror of `sourcenet::datapod::DataPod`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DataPod {
+    pub id: ObjectID,
+    pub datapod_id: String,
+    pub seller: SuiAddress,
+    pub title: String,
+    pub category: String,
+    pub description: String,
+    pub price_sui: u64,
+    pub data_hash: String,
+    pub blob_id: String,
+    pub kiosk_id: String,
+    pub status: u8,
+    pub total_sales: u64,
+    pub average_rating: u64,
+    pub created_at: u64,
+    pub published_at: u64,
+}
+
+/// Mirror of `sourcenet::datapod::DataPodOwnerCap`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DataPodOwnerCap {
+    pub id: ObjectID,
+    pub datapod_id: SuiAddress,
+}
+
+/// Mirror of `sourcenet::purchase::PurchaseRequest`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PurchaseRequest {
+    pub id: ObjectID,
+    pub purchase_id: String,
+    pub datapod_id: String,
+    pub buyer: SuiAddress,
+    pub seller: SuiAddress,
+    pub buyer_public_key: String,
+    pub price_sui: u64,
+    pub data_hash: String,
+    pub status: u8,
+    pub created_at: u64,
+    pub completed_at: u64,
+}
+
+/// Mirror of `sourcenet::purchase::PurchaseOwnerCap`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PurchaseOwnerCap {
+    pub id: ObjectID,
+    pub purchase_id: SuiAddress,
+}
+
+/// Mirror of `sui::balance::Balance<T>`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Balance {
+    pub value: u64,
+}
+
+/// Mirror of `sourcenet::escrow::Escrow`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Escrow {
+    pub id: ObjectID,
+    pub purchase_id: String,
+    pub buyer: SuiAddress,
+    pub seller: SuiAddress,
+    pub amount: u64,
+    pub data_hash: String,
+    pub status: u8,
+    pub created_at: u64,
+    pub balance: Balance,
+}
+
+/// Mirror of `sourcenet::escrow::EscrowOwnerCap`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EscrowOwnerCap {
+    pub id: ObjectID,
+    pub escrow_id: SuiAddress,
+}
+
+/// Render the BCS `contents` of a SourceNet object as JSON. Returns `None` for
+/// structs that are not SourceNet object types.
+pub fn to_json(module: &str, name: &str, contents: &[u8]) -> Result<Option<serde_json::Value>> {
+    let json = match (module, name) {
+        (DATAPOD_MODULE, "DataPod") => render::<DataPod>(name, contents)?,
+        (DATAPOD_MODULE, "DataPodOwnerCap") => render::<DataPodOwnerCap>(name, contents)?,
+        (PURCHASE_MODULE, "PurchaseRequest") => render::<PurchaseRequest>(name, contents)?,
+        (PURCHASE_MODULE, "PurchaseOwnerCap") => render::<PurchaseOwnerCap>(name, contents)?,
+        (ESCROW_MODULE, "Escrow") => render::<Escrow>(name, contents)?,
+        (ESCROW_MODULE, "EscrowOwnerCap") => render::<EscrowOwnerCap>(name, contents)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(json))
+}
+
+fn render<T>(name: &str, contents: &[u8]) -> Result<serde_json::Value>
+where
+    T: for<'de> Deserialize<'de> + Serialize,
+{
+    let value: T = bcs::from_bytes(contents)
+        .with_context(|| format!("Failed to deserialize {name} object"))?;
+    serde_json::to_value(value).with_context(|| format!("Failed to render {name} object as JSON"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn id(last: u8) -> ObjectID {
+        let mut bytes = [0; 32];
+        bytes[31] = last;
+        ObjectID::new(bytes)
+    }
+
+    fn address(last: u8) -> SuiAddress {
+        id(last).into()
+    }
+
+    /// BCS encoding of a Move struct with these fields, in order
+    fn contents<T: Serialize>(fields: T) -> Vec<u8> {
+        bcs::to_bytes(&fields).unwrap()
+    }
+
+    fn render(module: &str, name: &str, contents: &[u8]) -> serde_json::Value {
+        to_json(module, name, contents).unwrap().expect("a SourceNet object type")
+    }
+
+    #[test]
+    fn datapods_render_as_json() {
+        let bytes = contents((
+            id(1),
+            "listing-42",
+            address(2),
+            "Weather",
+            "climate",
+            "Hourly readings",
+            10u64,
+            "hash",
+            "blob",
+            "0xk",
+            1u8,
+            3u64,
+            45u64,
+            1_700_000_000_000u64,
+            1_700_000_100_000u64,
+        ));
+        assert_eq!(
+            render(DATAPOD_MODULE, "DataPod", &bytes),
+            json!({
+                "id": id(1),
+                "datapod_id": "listing-42",
+                "seller": address(2),
+                "title": "Weather",
+                "category": "climate",
+                "description": "Hourly readings",
+                "price_sui": 10,
+                "data_hash": "hash",
+                "blob_id": "blob",
+                "kiosk_id": "0xk",
+                "status": 1,
+                "total_sales": 3,
+                "average_rating": 45,
+                "created_at": 1_700_000_000_000u64,
+                "published_at": 1_700_000_100_000u64,
+            }),
+        );
+    }
+
+    #[test]
+    fn owner_caps_render_as_json() {
+        let bytes = contents((id(1), address(2)));
+        for (module, name, field) in [
+            (DATAPOD_MODULE, "DataPodOwnerCap", "datapod_id"),
+            (PURCHASE_MODULE, "PurchaseOwnerCap", "purchase_id"),
+            (ESCROW_MODULE, "EscrowOwnerCap", "escrow_id"),
+        ] {
+            assert_eq!(render(module, name, &bytes), json!({ "id": id(1), field: address(2) }));
+        }
+    }
+
+    #[test]
+    fn purchase_requests_render_as_json() {
+        let bytes = contents((
+            id(1),
+            "purchase-7",
+            "listing-42",
+            address(2),
+            address(3),
+            "pk",
+            u64::MAX,
+            "hash",
+            2u8,
+            1_700_000_000_000u64,
+            0u64,
+        ));
+        assert_eq!(
+            render(PURCHASE_MODULE, "PurchaseRequest", &bytes),
+            json!({
+                "id": id(1),
+                "purchase_id": "purchase-7",
+                "datapod_id": "listing-42",
+                "buyer": address(2),
+                "seller": address(3),
+                "buyer_public_key": "pk",
+                "price_sui": u64::MAX,
+                "data_hash": "hash",
+                "status": 2,
+                "created_at": 1_700_000_000_000u64,
+                "completed_at": 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn escrows_render_their_balance() {
+        let bytes = contents((
+            id(1),
+            "purchase-7",
+            address(2),
+            address(3),
+            10u64,
+            "hash",
+            0u8,
+            1_700_000_000_000u64,
+            // `Balance<SUI>` holds a single `u64`
+            10u64,
+        ));
+        assert_eq!(
+            render(ESCROW_MODULE, "Escrow", &bytes),
+            json!({
+                "id": id(1),
+                "purchase_id": "purchase-7",
+                "buyer": address(2),
+                "seller": address(3),
+                "amount": 10,
+                "data_hash": "hash",
+                "status": 0,
+                "created_at": 1_700_000_000_000u64,
+                "balance": { "value": 10 },
+            }),
+        );
+    }
+
+    #[test]
+    fn other_types_and_bad_contents() {
+        let bytes = contents((id(1), address(2)));
+        assert!(to_json(DATAPOD_MODULE, "Kiosk", &bytes).unwrap().is_none());
+        assert!(to_json(ESCROW_MODULE, "DataPodOwnerCap", &bytes).unwrap().is_none());
+
+        assert!(to_json(DATAPOD_MODULE, "DataPodOwnerCap", &bytes[..40]).is_err());
+        assert!(to_json(ESCROW_MODULE, "Escrow", &bytes).is_err());
+    }
+}
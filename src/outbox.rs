@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use diesel::dsl::sql;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use sui_indexer_alt_framework::postgres::Connection;
+use url::Url;
+
+use crate::cli::OutboxCommand;
+use crate::models::{rows_per_insert, Address, Digest, StoredEvent, StoredOutboxEvent};
+use crate::schema::{outbox, outbox_consumer_offsets};
+
+/// Entries are read in `(txid, sequence)` order, and only those appended by transactions older
+/// than any still running. Whichever order transactions commit in, none can then add an entry
+/// before one a consumer has read.
+const COMMITTED: &str = "txid < pg_snapshot_xmin(pg_current_snapshot())::text::bigint";
+
+/// Append `events` to the outbox. Called from `Handler::commit`, so the rows are written in the
+/// same transaction as the events, and replaying a batch does not append them twice.
+pub async fn append<E: StoredEvent>(conn: &mut Connection<'_>, events: &[E]) -> Result<usize> {
+    if events.is_empty() {
+        return Ok(0);
+    }
+
+    let rows = events
+        .iter()
+        .map(|event| {
+            let description = event.describe();
+            Ok(StoredOutboxEvent {
+                module: description.module.to_string(),
+                event_type: description.event_type.to_string(),
+                entity_id: description.entity_id,
+                transaction_digest: description.transaction_digest,
+                event_index: description.event_index,
+                checkpoint_sequence_number: description.checkpoint_sequence_number,
+                timestamp: description.timestamp,
+                payload: serde_json::to_value(event)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut inserted = 0;
+    for chunk in rows.chunks(rows_per_insert::<StoredOutboxEvent>()) {
+        inserted += diesel::insert_into(outbox::table)
+            .values(chunk)
+            .on_conflict((outbox::transaction_digest, outbox::event_index))
+            .do_nothing()
+            .execute(conn)
+            .await?;
+    }
+
+    Ok(inserted)
+}
+
+/// A row of `outbox`
+#[derive(Queryable, Selectable, Serialize, Debug)]
+#[diesel(table_name = outbox)]
+pub struct OutboxEntry {
+    pub sequence: i64,
+    pub module: String,
+    pub event_type: String,
+    pub entity_id: Address,
+    pub transaction_digest: Digest,
+    pub event_index: i64,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp: i64,
+    pub payload: serde_json::Value,
+}
+
+/// The `(txid, sequence)` of the last entry `group` acknowledged, or `(0, 0)` for a group that
+/// never did
+pub async fn offset(conn: &mut AsyncPgConnection, group: &str) -> Result<(i64, i64)> {
+    let acked = outbox_consumer_offsets::table
+        .find(group.to_string())
+        .select((outbox_consumer_offsets::acked_txid, outbox_consumer_offsets::acked_sequence))
+        .first(conn)
+        .await
+        .optional()?;
+    Ok(acked.unwrap_or((0, 0)))
+}
+
+/// Entries after the `(txid, sequence)` offset `acked`
+fn after(acked: (i64, i64)) -> Box<dyn BoxableExpression<outbox::table, Pg, SqlType = Bool>> {
+    Box::new(
+        sql::<Bool>("(txid, sequence) > (")
+            .bind::<BigInt, _>(acked.0)
+            .sql(", ")
+            .bind::<BigInt, _>(acked.1)
+            .sql(")"),
+    )
+}
+
+/// Up to `limit` committed entries after the last one `group` acknowledged. Reading does not
+/// move the offset: entries are returned again until they are acknowledged, so each is
+/// delivered at least once.
+pub async fn poll(
+    conn: &mut AsyncPgConnection,
+    group: &str,
+    limit: i64,
+) -> Result<Vec<OutboxEntry>> {
+    let acked = offset(conn, group).await?;
+    Ok(outbox::table
+        .filter(after(acked))
+        .filter(sql::<Bool>(COMMITTED))
+        .select(OutboxEntry::as_select())
+        .order((outbox::txid, outbox::sequence))
+        .limit(limit)
+        .load(conn)
+        .await?)
+}
+
+/// Acknowledge every entry up to and including the one at `sequence` for `group`. Offsets never
+/// move backwards, so a late or repeated acknowledgement is harmless.
+pub async fn ack(conn: &mut AsyncPgConnection, group: &str, sequence: i64) -> Result<()> {
+    let txid: i64 = outbox::table
+        .find(sequence)
+        .select(outbox::txid)
+        .first(conn)
+        .await
+        .optional()?
+        .with_context(|| format!("No outbox entry with sequence {sequence}"))?;
+
+    use outbox_consumer_offsets::dsl::*;
+    diesel::insert_into(outbox_consumer_offsets)
+        .values((consumer_group.eq(group), acked_txid.eq(txid), acked_sequence.eq(sequence)))
+        .on_conflict(consumer_group)
+        .do_update()
+        .set((
+            acked_txid.eq(sql("excluded.acked_txid")),
+            acked_sequence.eq(sql("excluded.acked_sequence")),
+            updated_at.eq(diesel::dsl::now.nullable()),
+        ))
+        .filter(sql::<Bool>(
+            "(excluded.acked_txid, excluded.acked_sequence) \
+             > (outbox_consumer_offsets.acked_txid, outbox_consumer_offsets.acked_sequence)",
+        ))
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Run an `outbox` subcommand
+pub async fn run(database_url: Url, command: OutboxCommand) -> Result<()> {
+    let mut conn = AsyncPgConnection::establish(database_url.as_str())
+        .await
+        .context("Failed to connect to the database")?;
+
+    match command {
+        OutboxCommand::Consume { group, limit } => {
+            for entry in poll(&mut conn, &group, limit).await? {
+                println!("{}", serde_json::to_string(&entry)?);
+            }
+        }
+
+        OutboxCommand::Ack { group, sequence } => ack(&mut conn, &group, sequence).await?,
+
+        OutboxCommand::Groups => {
+            let groups: Vec<(String, i64, i64)> = outbox_consumer_offsets::table
+                .select((
+                    outbox_consumer_offsets::consumer_group,
+                    outbox_consumer_offsets::acked_txid,
+                    outbox_consumer_offsets::acked_sequence,
+                ))
+                .order(outbox_consumer_offsets::consumer_group)
+                .load(&mut conn)
+                .await?;
+
+            for (group, txid, sequence) in groups {
+                let pending: i64 = outbox::table
+                    .filter(after((txid, sequence)))
+                    .count()
+                    .get_result(&mut conn)
+                    .await?;
+                println!("{group}\tacked={sequence}\tpending={pending}");
+            }
+        }
+    }
+
+    Ok(())
+}
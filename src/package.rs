@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::StructTag;
+use sui_types::base_types::ObjectID;
+use sui_types::event::Event;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
+use sui_types::transaction::TransactionDataAPI;
+
+use crate::cli::TransactionFilter;
+
+/// Module names of the SourceNet Move package
+pub const DATAPOD_MODULE: &str = "datapod";
+pub const PURCHASE_MODULE: &str = "purchase";
+pub const ESCROW_MODULE: &str = "escrow";
+
+/// Symbolic name of abort `code` raised by `module`, from the error constants of the Move source
+pub fn abort_error_name(module: &str, code: u64) -> Option<&'static str> {
+    let name = match (module, code) {
+        (DATAPOD_MODULE, 1) => "EInvalidStatus",
+        (DATAPOD_MODULE, 2) => "EUnauthorized",
+        (DATAPOD_MODULE, 3) => "EInvalidPrice",
+        (DATAPOD_MODULE, 4) => "EInvalidTitle",
+        (PURCHASE_MODULE, 1) => "EInvalidStatus",
+        (PURCHASE_MODULE, 2) => "EUnauthorized",
+        (PURCHASE_MODULE, 3) => "EInvalidPrice",
+        (ESCROW_MODULE, 1) => "EInvalidStatus",
+        (ESCROW_MODULE, 2) => "EUnauthorized",
+        (ESCROW_MODULE, 3) => "EInsufficientFunds",
+        (ESCROW_MODULE, 4) => "EInvalidAmount",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// The deployed SourceNet package whose events and objects are indexed.
+///
+/// Event and object types always carry the package ID the type was first published at,
+/// so this must be the original package ID even after upgrades. Calls go to the ID of the
+/// version they execute, so those of upgraded versions are listed as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceNetPackage {
+    id: ObjectID,
+    upgrades: Arc<[ObjectID]>,
+}
+
+impl SourceNetPackage {
+    /// Read the package ID from `SMART_CONTRACT_ADDRESS`, and the IDs of its upgraded versions
+    /// from the comma-separated `SMART_CONTRACT_UPGRADES`, if set
+    pub fn from_env() -> Result<Self> {
+        let address = std::env::var("SMART_CONTRACT_ADDRESS")
+            .context("SMART_CONTRACT_ADDRESS must be set in the environment")?;
+        let package = Self::parse(&address)?;
+
+        match std::env::var("SMART_CONTRACT_UPGRADES") {
+            Ok(upgrades) => package.with_upgrades(&upgrades),
+            Err(_) => Ok(package),
+        }
+    }
+
+    pub fn parse(address: &str) -> Result<Self> {
+        Ok(Self {
+            id: parse_id(address)?,
+            upgrades: Arc::from([]),
+        })
+    }
+
+    /// Also match calls into the upgraded versions in the comma-separated `addresses`
+    pub fn with_upgrades(self, addresses: &str) -> Result<Self> {
+        let upgrades = addresses
+            .split(',')
+            .filter(|address| !address.trim().is_empty())
+            .map(parse_id)
+            .collect::<Result<_>>()?;
+        Ok(Self { upgrades, ..self })
+    }
+
+    /// Whether `address` is this package. Modules keep the original package ID as their
+    /// address across upgrades, so this also holds for abort locations in upgraded code.
+    pub fn is(&self, address: AccountAddress) -> bool {
+        ObjectID::from(address) == self.id
+    }
+
+    /// Whether `tag` is a struct defined in this package
+    pub fn defines(&self, tag: &StructTag) -> bool {
+        self.is(tag.address)
+    }
+
+    /// Whether `event` is a struct defined in `module` of this package
+    pub fn emits(&self, event: &Event, module: &str) -> bool {
+        self.defines(&event.type_) && event.type_.module.as_str() == module
+    }
+
+    /// Whether `id` is the ID of any version of this package
+    pub fn is_version(&self, id: &ObjectID) -> bool {
+        *id == self.id || self.upgrades.contains(id)
+    }
+
+    /// Whether the programmable transaction of `tx` calls a function of any version of this
+    /// package
+    pub fn called_by(&self, tx: &CheckpointTransaction) -> bool {
+        tx.transaction
+            .transaction_data()
+            .move_calls()
+            .into_iter()
+            .any(|(package, _, _)| self.is_version(package))
+    }
+
+    /// Whether `tx` emitted an event defined in this package
+    pub fn emitted_by(&self, tx: &CheckpointTransaction) -> bool {
+        tx.events
+            .iter()
+            .flat_map(|evs| evs.data.iter())
+            .any(|event| self.defines(&event.type_))
+    }
+
+    /// Whether `tx` read or wrote an object whose type is defined in this package
+    pub fn touched_by(&self, tx: &CheckpointTransaction) -> bool {
+        tx.input_objects
+            .iter()
+            .chain(tx.output_objects.iter())
+            .filter_map(|object| object.struct_tag())
+            .any(|tag| self.defines(&tag))
+    }
+
+    /// Whether `tx` is kept by `filter`
+    pub fn matches(&self, tx: &CheckpointTransaction, filter: TransactionFilter) -> bool {
+        match filter {
+            TransactionFilter::All => true,
+            TransactionFilter::Calls => self.called_by(tx),
+            TransactionFilter::Events => self.emitted_by(tx),
+            TransactionFilter::Objects => self.touched_by(tx),
+            TransactionFilter::SourceNet => {
+                self.called_by(tx) || self.emitted_by(tx) || self.touched_by(tx)
+            }
+        }
+    }
+}
+
+fn parse_id(address: &str) -> Result<ObjectID> {
+    ObjectID::from_hex_literal(address.trim())
+        .with_context(|| format!("Invalid SourceNet package address: {address:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abort_codes_name_the_module_error_constants() {
+        assert_eq!(abort_error_name(DATAPOD_MODULE, 3), Some("EInvalidPrice"));
+        assert_eq!(abort_error_name(PURCHASE_MODULE, 2), Some("EUnauthorized"));
+        assert_eq!(abort_error_name(ESCROW_MODULE, 3), Some("EInsufficientFunds"));
+        assert_eq!(abort_error_name(ESCROW_MODULE, 4), Some("EInvalidAmount"));
+
+        // The same code means different things in different modules
+        assert_eq!(abort_error_name(DATAPOD_MODULE, 4), Some("EInvalidTitle"));
+        assert_eq!(abort_error_name(PURCHASE_MODULE, 4), None);
+    }
+
+    #[test]
+    fn unknown_abort_codes_have_no_name() {
+        for module in [DATAPOD_MODULE, PURCHASE_MODULE, ESCROW_MODULE] {
+            assert_eq!(abort_error_name(module, 0), None);
+            assert_eq!(abort_error_name(module, u64::MAX), None);
+        }
+        assert_eq!(abort_error_name("kiosk", 1), None);
+    }
+}
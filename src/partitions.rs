@@ -0,0 +1,162 @@
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel::sql_types::Text;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use sui_indexer_alt_framework::postgres::Connection;
+use url::Url;
+
+use crate::cli::{PartitionCommand, PartitionedTable};
+use crate::schema::checkpoint_partitions;
+
+/// Checkpoints covered by a partition when none is configured
+pub const DEFAULT_PARTITION_SIZE: i64 = 1_000_000;
+
+/// Checkpoints covered by each new partition. `Handler::commit` has no access to the handler,
+/// so this is process-wide and set once at startup.
+static PARTITION_SIZE: OnceLock<i64> = OnceLock::new();
+
+pub fn configure(checkpoints_per_partition: i64) {
+    let _ = PARTITION_SIZE.set(checkpoints_per_partition);
+}
+
+/// Make sure `table` has partitions for `checkpoints`, and for the partition after the last of
+/// them, so that ingestion finds the next partition already there. Called from
+/// `Handler::commit` before inserting. Only a batch that needs a new partition takes the lock
+/// that serializes creating them, which is held until it commits.
+pub async fn prepare(
+    conn: &mut Connection<'_>,
+    table: PartitionedTable,
+    checkpoints: impl IntoIterator<Item = i64>,
+) -> Result<()> {
+    let mut checkpoints = checkpoints.into_iter();
+    let Some(first) = checkpoints.next() else {
+        return Ok(());
+    };
+    let (lo, hi) = checkpoints.fold((first, first), |(lo, hi), cp| (lo.min(cp), hi.max(cp)));
+    let size = *PARTITION_SIZE.get().unwrap_or(&DEFAULT_PARTITION_SIZE);
+
+    diesel::sql_query("SELECT ensure_checkpoint_partitions($1, $2, $3, $4)")
+        .bind::<Text, _>(table.name())
+        .bind::<BigInt, _>(lo)
+        .bind::<BigInt, _>(hi.saturating_add(size))
+        .bind::<BigInt, _>(size)
+        .execute(conn)
+        .await
+        .with_context(|| format!("Failed to create partitions of {}", table.name()))?;
+
+    Ok(())
+}
+
+/// A row of `checkpoint_partitions`
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = checkpoint_partitions)]
+struct Partition {
+    partition_name: String,
+    parent_table: String,
+    from_checkpoint: i64,
+    to_checkpoint: i64,
+    detached: bool,
+}
+
+/// Partitions of `table` that only hold checkpoints before `before`, oldest first
+async fn partitions_before(
+    conn: &mut AsyncPgConnection,
+    table: PartitionedTable,
+    before: i64,
+) -> Result<Vec<Partition>> {
+    Ok(checkpoint_partitions::table
+        .filter(checkpoint_partitions::parent_table.eq(table.name()))
+        .filter(checkpoint_partitions::to_checkpoint.le(before))
+        .select(Partition::as_select())
+        .order(checkpoint_partitions::from_checkpoint)
+        .load(conn)
+        .await?)
+}
+
+/// Drop the partitions of `table` that only hold checkpoints before `before`, and detached ones
+/// as well when `include_detached` is set. Returns the names of the dropped partitions.
+pub async fn drop_before(
+    conn: &mut AsyncPgConnection,
+    table: PartitionedTable,
+    before: i64,
+    include_detached: bool,
+) -> Result<Vec<String>> {
+    let mut dropped = vec![];
+    for p in partitions_before(conn, table, before).await? {
+        if p.detached && !include_detached {
+            continue;
+        }
+
+        diesel::sql_query(format!(r#"DROP TABLE IF EXISTS "{}""#, p.partition_name))
+            .execute(conn)
+            .await?;
+
+        diesel::delete(checkpoint_partitions::table.find(&p.partition_name))
+            .execute(conn)
+            .await?;
+        dropped.push(p.partition_name);
+    }
+
+    Ok(dropped)
+}
+
+/// Run a `partitions` subcommand
+pub async fn run(database_url: Url, command: PartitionCommand) -> Result<()> {
+    let mut conn = AsyncPgConnection::establish(database_url.as_str())
+        .await
+        .context("Failed to connect to the database")?;
+
+    match command {
+        PartitionCommand::List => {
+            let partitions: Vec<Partition> = checkpoint_partitions::table
+                .select(Partition::as_select())
+                .order((checkpoint_partitions::parent_table, checkpoint_partitions::from_checkpoint))
+                .load(&mut conn)
+                .await?;
+
+            for p in partitions {
+                println!(
+                    "{}\t{}\t[{}, {}){}",
+                    p.parent_table,
+                    p.partition_name,
+                    p.from_checkpoint,
+                    p.to_checkpoint,
+                    if p.detached { "\tdetached" } else { "" },
+                );
+            }
+        }
+
+        PartitionCommand::Detach { table, before } => {
+            for p in partitions_before(&mut conn, table, before).await? {
+                if p.detached {
+                    continue;
+                }
+
+                // Names come from `checkpoint_partitions`, which only holds names we generated
+                diesel::sql_query(format!(
+                    r#"ALTER TABLE "{}" DETACH PARTITION "{}""#,
+                    p.parent_table, p.partition_name
+                ))
+                .execute(&mut conn)
+                .await?;
+
+                diesel::update(checkpoint_partitions::table.find(&p.partition_name))
+                    .set(checkpoint_partitions::detached.eq(true))
+                    .execute(&mut conn)
+                    .await?;
+                println!("Detached {}", p.partition_name);
+            }
+        }
+
+        PartitionCommand::Drop { table, before } => {
+            for name in drop_before(&mut conn, table, before, true).await? {
+                println!("Dropped {name}");
+            }
+        }
+    }
+
+    Ok(())
+}
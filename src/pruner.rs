@@ -0,0 +1,247 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Text};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use sui_indexer_alt_framework::pipeline::Processor;
+use tracing::{error, warn};
+use url::Url;
+
+use crate::cli::{PartitionedTable, Pipeline, Retention, RetentionPolicy};
+use crate::handlers::{
+    DataPodEventHandler, EscrowEventHandler, FailedTransactionHandler, MoveCallHandler,
+    ObjectVersionHandler, PurchaseEventHandler, TransactionDigestHandler,
+};
+use crate::partitions;
+
+/// Append-only table written by a pipeline, which the pruner can delete old rows from
+#[derive(Debug, Clone, Copy)]
+pub struct PrunedTable {
+    pub name: &'static str,
+    /// Column holding the checkpoint timestamp in milliseconds, if the table has one
+    pub timestamp: Option<&'static str>,
+    /// Name of the pipeline in the framework's `watermarks` table
+    pipeline: &'static str,
+    partitioned: Option<PartitionedTable>,
+}
+
+/// The table `pipeline` appends to, or `None` for pipelines that keep current state, which
+/// must not be pruned
+pub fn table(pipeline: Pipeline) -> Option<PrunedTable> {
+    let (name, timestamp, pipeline, partitioned) = match pipeline {
+        Pipeline::TransactionDigests => (
+            "transaction_digests",
+            Some("timestamp"),
+            TransactionDigestHandler::NAME,
+            Some(PartitionedTable::TransactionDigests),
+        ),
+        Pipeline::DatapodEvents => (
+            "datapod_events",
+            Some("timestamp"),
+            DataPodEventHandler::NAME,
+            Some(PartitionedTable::DatapodEvents),
+        ),
+        Pipeline::PurchaseEvents => {
+            ("purchase_events", Some("timestamp"), PurchaseEventHandler::NAME, None)
+        }
+        Pipeline::EscrowEvents => {
+            ("escrow_events", Some("timestamp"), EscrowEventHandler::NAME, None)
+        }
+        Pipeline::ObjectVersions => (
+            "object_versions",
+            None,
+            ObjectVersionHandler::NAME,
+            Some(PartitionedTable::ObjectVersions),
+        ),
+        Pipeline::MoveCalls => ("move_calls", Some("timestamp"), MoveCallHandler::NAME, None),
+        Pipeline::FailedTransactions => (
+            "failed_transactions",
+            Some("timestamp"),
+            FailedTransactionHandler::NAME,
+            None,
+        ),
+        Pipeline::SmartContractObjects => return None,
+    };
+
+    Some(PrunedTable { name, timestamp, pipeline, partitioned })
+}
+
+#[derive(QueryableByName, Debug)]
+struct Checkpoint {
+    #[diesel(sql_type = Nullable<BigInt>)]
+    checkpoint: Option<i64>,
+}
+
+/// Prune the tables of `policies` every `interval` until the process is stopped. A failure is
+/// reported and retried on the next round, so it never stops the indexer.
+pub async fn run(
+    database_url: Url,
+    policies: Vec<RetentionPolicy>,
+    interval: Duration,
+    batch_size: i64,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let mut conn = match AsyncPgConnection::establish(database_url.as_str()).await {
+            Ok(conn) => conn,
+            Err(err) => {
+                warn!("Pruner failed to connect to the database: {err:#}");
+                continue;
+            }
+        };
+
+        for policy in &policies {
+            let Some(table) = table(policy.pipeline) else {
+                continue;
+            };
+
+            if let Err(err) = prune(&mut conn, table, policy.retention, batch_size).await {
+                error!(table = table.name, "Failed to prune: {err:#}");
+            }
+        }
+    }
+}
+
+/// Delete the rows of `table` past `retention`, at most about `batch_size` rows per statement.
+/// Partitions that only hold such rows are dropped instead. The low watermarks readers go by,
+/// ours and the framework's `reader_lo`, are advanced and committed first, so readers never look
+/// for rows that are being removed. The framework's `pruner_hi` follows each delete.
+async fn prune(
+    conn: &mut AsyncPgConnection,
+    table: PrunedTable,
+    retention: Retention,
+    batch_size: i64,
+) -> Result<()> {
+    let Some(cutoff) = cutoff(conn, table, retention).await? else {
+        return Ok(());
+    };
+
+    diesel::sql_query(
+        "WITH framework AS ( \
+             UPDATE watermarks SET \
+                 reader_lo = GREATEST(reader_lo, $2), \
+                 pruner_timestamp = NOW() \
+             WHERE pipeline = $3 \
+         ) \
+         INSERT INTO pruner_watermarks (table_name, pruned_before_checkpoint) \
+         VALUES ($1, $2) \
+         ON CONFLICT (table_name) DO UPDATE SET \
+             pruned_before_checkpoint = GREATEST( \
+                 pruner_watermarks.pruned_before_checkpoint, \
+                 EXCLUDED.pruned_before_checkpoint \
+             ), \
+             updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind::<Text, _>(table.name)
+    .bind::<BigInt, _>(cutoff)
+    .bind::<Text, _>(table.pipeline)
+    .execute(conn)
+    .await
+    .with_context(|| format!("Failed to advance the low watermark to checkpoint {cutoff}"))?;
+
+    if let Some(partitioned) = table.partitioned {
+        partitions::drop_before(conn, partitioned, cutoff, false).await?;
+    }
+
+    loop {
+        // Delete up to the checkpoint of the row `batch_size` rows in, and the rest of that
+        // checkpoint, so that every batch makes progress however many rows a checkpoint has
+        let next: Option<Checkpoint> = diesel::sql_query(format!(
+            "SELECT checkpoint_sequence_number AS checkpoint FROM {} \
+             WHERE checkpoint_sequence_number < $1 \
+             ORDER BY checkpoint_sequence_number \
+             OFFSET $2 LIMIT 1",
+            table.name
+        ))
+        .bind::<BigInt, _>(cutoff)
+        .bind::<BigInt, _>(batch_size)
+        .get_result(conn)
+        .await
+        .optional()?;
+
+        let end = next
+            .and_then(|row| row.checkpoint)
+            .map_or(cutoff, |checkpoint| cutoff.min(checkpoint + 1));
+
+        diesel::sql_query(format!(
+            "WITH pruned AS ( \
+                 DELETE FROM {} WHERE checkpoint_sequence_number < $1 \
+             ) \
+             UPDATE watermarks SET pruner_hi = GREATEST(pruner_hi, $1) WHERE pipeline = $2",
+            table.name
+        ))
+        .bind::<BigInt, _>(end)
+        .bind::<Text, _>(table.pipeline)
+        .execute(conn)
+        .await
+        .with_context(|| format!("Failed to delete rows before checkpoint {end}"))?;
+
+        if end >= cutoff {
+            return Ok(());
+        }
+    }
+}
+
+/// Last checkpoint the pipeline of `table` has committed, with every checkpoint before it
+async fn committed_hi(conn: &mut AsyncPgConnection, table: PrunedTable) -> Result<Option<i64>> {
+    let committed: Option<Checkpoint> = diesel::sql_query(
+        "SELECT checkpoint_hi_inclusive AS checkpoint FROM watermarks WHERE pipeline = $1",
+    )
+    .bind::<Text, _>(table.pipeline)
+    .get_result(conn)
+    .await
+    .optional()?;
+
+    Ok(committed.and_then(|row| row.checkpoint))
+}
+
+/// First checkpoint whose rows `retention` keeps, or `None` when nothing is to be pruned yet
+async fn cutoff(
+    conn: &mut AsyncPgConnection,
+    table: PrunedTable,
+    retention: Retention,
+) -> Result<Option<i64>> {
+    match retention {
+        Retention::Checkpoints(checkpoints) => Ok(committed_hi(conn, table)
+            .await?
+            .map(|hi| hi + 1 - checkpoints)
+            .filter(|cutoff| *cutoff > 0)),
+
+        Retention::Age(age) => {
+            let Some(timestamp) = table.timestamp else {
+                return Ok(None);
+            };
+
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+            let oldest_kept = now.saturating_sub(age).as_millis() as i64;
+
+            // The newest row past retention, read from the end of the `(timestamp, checkpoint)`
+            // index. Timestamps never decrease with checkpoints, so every row of its checkpoint
+            // and the ones before is past retention too.
+            let last_pruned: Option<Checkpoint> = diesel::sql_query(format!(
+                "SELECT checkpoint_sequence_number AS checkpoint FROM {0} \
+                 WHERE {1} < $1 \
+                 ORDER BY {1} DESC, checkpoint_sequence_number DESC \
+                 LIMIT 1",
+                table.name, timestamp
+            ))
+            .bind::<BigInt, _>(oldest_kept)
+            .get_result(conn)
+            .await
+            .optional()?;
+
+            // A concurrent pipeline may have committed rows above a checkpoint it has not
+            // committed yet, so the cutoff stays within its watermark
+            let Some(hi) = committed_hi(conn, table).await? else {
+                return Ok(None);
+            };
+
+            Ok(last_pruned.and_then(|row| row.checkpoint).map(|checkpoint| hi.min(checkpoint + 1)))
+        }
+    }
+}
@@ -1,18 +1,180 @@
 // @generated automatically by Diesel CLI.
 
 diesel::table! {
-    datapod_events (id) {
+    checkpoint_partitions (partition_name) {
+        partition_name -> Varchar,
+        parent_table -> Varchar,
+        from_checkpoint -> BigInt,
+        to_checkpoint -> BigInt,
+        detached -> Bool,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    datapod_events (id, checkpoint_sequence_number) {
         id -> BigSerial,
         event_type -> Varchar,
-        datapod_id -> Varchar,
-        seller -> Varchar,
+        datapod_id -> Bytea,
+        seller -> Nullable<Bytea>,
         title -> Nullable<Varchar>,
         category -> Nullable<Varchar>,
-        price_sui -> Nullable<BigInt>,
+        price_sui -> Nullable<Numeric>,
         kiosk_id -> Nullable<Varchar>,
-        old_price -> Nullable<BigInt>,
-        new_price -> Nullable<BigInt>,
-        transaction_digest -> Varchar,
+        old_price -> Nullable<Numeric>,
+        new_price -> Nullable<Numeric>,
+        transaction_digest -> Bytea,
+        checkpoint_sequence_number -> BigInt,
+        event_index -> BigInt,
+        timestamp -> BigInt,
+        created_at -> Timestamp,
+        transaction_index -> BigInt,
+    }
+}
+
+diesel::table! {
+    datapods (datapod_id) {
+        datapod_id -> Bytea,
+        seller -> Nullable<Bytea>,
+        title -> Nullable<Varchar>,
+        category -> Nullable<Varchar>,
+        price_sui -> Nullable<Numeric>,
+        kiosk_id -> Nullable<Varchar>,
+        status -> Nullable<Varchar>,
+        created_checkpoint -> Nullable<BigInt>,
+        published_checkpoint -> Nullable<BigInt>,
+        last_updated_checkpoint -> BigInt,
+        last_event_index -> BigInt,
+        last_updated_timestamp -> BigInt,
+    }
+}
+
+diesel::table! {
+    escrow_events (id) {
+        id -> BigSerial,
+        event_type -> Varchar,
+        escrow_id -> Bytea,
+        purchase_id -> Nullable<Varchar>,
+        buyer -> Nullable<Bytea>,
+        seller -> Nullable<Bytea>,
+        amount -> Numeric,
+        transaction_digest -> Bytea,
+        checkpoint_sequence_number -> BigInt,
+        event_index -> BigInt,
+        timestamp -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    escrows (escrow_id) {
+        escrow_id -> Bytea,
+        purchase_id -> Nullable<Varchar>,
+        buyer -> Nullable<Bytea>,
+        seller -> Nullable<Bytea>,
+        amount -> Numeric,
+        status -> Varchar,
+        created_checkpoint -> Nullable<BigInt>,
+        created_timestamp -> Nullable<BigInt>,
+        released_checkpoint -> Nullable<BigInt>,
+        released_timestamp -> Nullable<BigInt>,
+        refunded_checkpoint -> Nullable<BigInt>,
+        refunded_timestamp -> Nullable<BigInt>,
+        last_updated_checkpoint -> BigInt,
+        last_event_index -> BigInt,
+        last_updated_timestamp -> BigInt,
+    }
+}
+
+diesel::table! {
+    failed_transactions (transaction_digest) {
+        transaction_digest -> Bytea,
+        sender -> Bytea,
+        error -> Text,
+        failed_command_index -> Nullable<BigInt>,
+        abort_module -> Nullable<Varchar>,
+        abort_function -> Nullable<Varchar>,
+        abort_code -> Nullable<Numeric>,
+        abort_error_name -> Nullable<Varchar>,
+        checkpoint_sequence_number -> BigInt,
+        timestamp -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    move_calls (transaction_digest, command_index) {
+        transaction_digest -> Bytea,
+        command_index -> BigInt,
+        package -> Bytea,
+        module -> Varchar,
+        function -> Varchar,
+        type_arguments -> Jsonb,
+        sender -> Bytea,
+        checkpoint_sequence_number -> BigInt,
+        timestamp -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    object_versions (object_id, version, checkpoint_sequence_number) {
+        object_id -> Bytea,
+        version -> Numeric,
+        digest -> Bytea,
+        object_type -> Varchar,
+        owner -> Nullable<Varchar>,
+        data -> Nullable<Jsonb>,
+        deletion_kind -> Nullable<Varchar>,
+        transaction_digest -> Bytea,
+        checkpoint_sequence_number -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    outbox (sequence) {
+        sequence -> BigSerial,
+        module -> Varchar,
+        event_type -> Varchar,
+        entity_id -> Bytea,
+        transaction_digest -> Bytea,
+        event_index -> BigInt,
+        checkpoint_sequence_number -> BigInt,
+        timestamp -> BigInt,
+        payload -> Jsonb,
+        created_at -> Nullable<Timestamp>,
+        txid -> BigInt,
+    }
+}
+
+diesel::table! {
+    outbox_consumer_offsets (consumer_group) {
+        consumer_group -> Varchar,
+        acked_sequence -> BigInt,
+        updated_at -> Nullable<Timestamp>,
+        acked_txid -> BigInt,
+    }
+}
+
+diesel::table! {
+    pruner_watermarks (table_name) {
+        table_name -> Varchar,
+        pruned_before_checkpoint -> BigInt,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    purchase_events (id) {
+        id -> BigSerial,
+        event_type -> Varchar,
+        purchase_id -> Bytea,
+        datapod_id -> Nullable<Varchar>,
+        buyer -> Bytea,
+        seller -> Nullable<Bytea>,
+        price_sui -> Nullable<Numeric>,
+        transaction_digest -> Bytea,
         checkpoint_sequence_number -> BigInt,
         event_index -> BigInt,
         timestamp -> BigInt,
@@ -20,33 +182,112 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    purchases (purchase_id) {
+        purchase_id -> Bytea,
+        datapod_id -> Nullable<Varchar>,
+        buyer -> Bytea,
+        seller -> Nullable<Bytea>,
+        price_sui -> Nullable<Numeric>,
+        status -> Varchar,
+        created_checkpoint -> Nullable<BigInt>,
+        created_timestamp -> Nullable<BigInt>,
+        completed_timestamp -> Nullable<BigInt>,
+        refunded_timestamp -> Nullable<BigInt>,
+        disputed_timestamp -> Nullable<BigInt>,
+        last_updated_checkpoint -> BigInt,
+        last_event_index -> BigInt,
+        last_updated_timestamp -> BigInt,
+    }
+}
+
 diesel::table! {
     smart_contract_objects (id) {
         id -> BigSerial,
-        object_id -> Varchar,
+        object_id -> Bytea,
         object_type -> Varchar,
         owner -> Nullable<Varchar>,
-        version -> BigInt,
-        digest -> Varchar,
+        version -> Numeric,
+        digest -> Bytea,
         content_type -> Nullable<Varchar>,
         data -> Nullable<Jsonb>,
         checkpoint_sequence_number -> BigInt,
-        transaction_digest -> Varchar,
+        transaction_digest -> Bytea,
         created_at -> Timestamp,
+        is_deleted -> Bool,
+        deletion_kind -> Nullable<Varchar>,
+        deleted_at_checkpoint -> Nullable<BigInt>,
+        deleted_transaction_digest -> Nullable<Bytea>,
     }
 }
 
 diesel::table! {
-    transaction_digests (id) {
+    transaction_digests (id, checkpoint_sequence_number) {
         id -> BigSerial,
-        tx_digest -> Varchar,
+        tx_digest -> Bytea,
         checkpoint_sequence_number -> BigInt,
         created_at -> Timestamp,
+        sender -> Nullable<Bytea>,
+        execution_status -> Nullable<Varchar>,
+        gas_budget -> Nullable<Numeric>,
+        computation_cost -> Nullable<Numeric>,
+        storage_cost -> Nullable<Numeric>,
+        storage_rebate -> Nullable<Numeric>,
+        non_refundable_storage_fee -> Nullable<Numeric>,
+        timestamp -> Nullable<BigInt>,
+        transaction_index -> Nullable<BigInt>,
     }
 }
 
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> BigSerial,
+        subscription_id -> BigInt,
+        event_type -> Varchar,
+        transaction_digest -> Bytea,
+        event_index -> BigInt,
+        checkpoint_sequence_number -> BigInt,
+        payload -> Jsonb,
+        status -> Varchar,
+        attempts -> Integer,
+        next_attempt_at -> Timestamp,
+        last_error -> Nullable<Text>,
+        delivered_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_subscriptions (id) {
+        id -> BigSerial,
+        url -> Text,
+        secret -> Text,
+        event_type -> Nullable<Varchar>,
+        seller -> Nullable<Varchar>,
+        datapod_id -> Nullable<Varchar>,
+        active -> Bool,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::joinable!(webhook_deliveries -> webhook_subscriptions (subscription_id));
+
 diesel::allow_tables_to_appear_in_same_query!(
+    checkpoint_partitions,
     datapod_events,
+    datapods,
+    escrow_events,
+    escrows,
+    failed_transactions,
+    move_calls,
+    object_versions,
+    outbox,
+    outbox_consumer_offsets,
+    pruner_watermarks,
+    purchase_events,
+    purchases,
     smart_contract_objects,
     transaction_digests,
+    webhook_deliveries,
+    webhook_subscriptions,
 );
@@ -0,0 +1,530 @@
+use std::borrow::Cow;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use diesel::dsl::{now, IntervalDsl};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, Integer, Jsonb, Text};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sui_indexer_alt_framework::postgres::Connection;
+use url::Url;
+
+use crate::cli::{DeliverArgs, WebhookCommand};
+use crate::models::{
+    rows_per_insert, Address, EventDescription, StoredEvent, StoredWebhookDelivery,
+    StoredWebhookSubscription,
+};
+use crate::schema::{webhook_deliveries, webhook_subscriptions};
+
+/// Delivery status values of `webhook_deliveries.status`
+const PENDING: &str = "pending";
+const DELIVERED: &str = "delivered";
+const DEAD: &str = "dead";
+
+/// The filters of an active subscription
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = webhook_subscriptions)]
+struct SubscriptionFilter {
+    id: i64,
+    event_type: Option<String>,
+    seller: Option<String>,
+    datapod_id: Option<String>,
+}
+
+impl SubscriptionFilter {
+    /// The filter with its IDs in normal form, which subscriptions stored before they were
+    /// normalized on subscribe may not be in
+    fn normalized(self) -> Self {
+        let normalize = |id: Option<String>| id.map(|id| normalize_id(&id).into_owned());
+        Self {
+            seller: normalize(self.seller),
+            datapod_id: normalize(self.datapod_id),
+            ..self
+        }
+    }
+
+    fn matches(&self, event: &EventDescription<'_>) -> bool {
+        let accepts_id = |filter: &Option<String>, value: Option<&str>| {
+            filter
+                .as_deref()
+                .is_none_or(|filter| value.is_some_and(|value| normalize_id(value) == filter))
+        };
+
+        self.event_type.as_deref().is_none_or(|filter| filter == event.event_type)
+            && accepts_id(&self.seller, event.seller.map(|seller| seller.to_string()).as_deref())
+            && accepts_id(&self.datapod_id, event.datapod_id.as_deref())
+    }
+}
+
+/// `id` as written by `Address` if it is an address or object ID, in whatever case or padding.
+/// Application-level IDs, which purchases may refer to listings by, are kept as they are.
+fn normalize_id(id: &str) -> Cow<'_, str> {
+    match id.parse::<Address>() {
+        Ok(address) => Cow::Owned(address.to_string()),
+        Err(_) => Cow::Borrowed(id),
+    }
+}
+
+/// Queue a delivery of each of `events` to every active subscription it matches. The serialized
+/// event is the request body. Called from
+/// `Handler::commit`, so deliveries are written in the same transaction as the events and
+/// replaying a batch does not queue them twice.
+pub async fn enqueue<E: StoredEvent>(conn: &mut Connection<'_>, events: &[E]) -> Result<usize> {
+    if events.is_empty() {
+        return Ok(0);
+    }
+
+    let subscriptions: Vec<SubscriptionFilter> = webhook_subscriptions::table
+        .filter(webhook_subscriptions::active.eq(true))
+        .select(SubscriptionFilter::as_select())
+        .load(conn)
+        .await?
+        .into_iter()
+        .map(SubscriptionFilter::normalized)
+        .collect();
+
+    let mut deliveries = Vec::new();
+    for event in events {
+        let description = event.describe();
+        let matching: Vec<_> = subscriptions
+            .iter()
+            .filter(|subscription| subscription.matches(&description))
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+
+        let payload = serde_json::to_value(event)?;
+        deliveries.extend(matching.into_iter().map(|subscription| StoredWebhookDelivery {
+            subscription_id: subscription.id,
+            event_type: description.event_type.to_string(),
+            transaction_digest: description.transaction_digest,
+            event_index: description.event_index,
+            checkpoint_sequence_number: description.checkpoint_sequence_number,
+            payload: payload.clone(),
+        }));
+    }
+
+    if deliveries.is_empty() {
+        return Ok(0);
+    }
+
+    let mut inserted = 0;
+    for chunk in deliveries.chunks(rows_per_insert::<StoredWebhookDelivery>()) {
+        inserted += diesel::insert_into(webhook_deliveries::table)
+            .values(chunk)
+            .on_conflict((
+                webhook_deliveries::subscription_id,
+                webhook_deliveries::transaction_digest,
+                webhook_deliveries::event_index,
+            ))
+            .do_nothing()
+            .execute(conn)
+            .await?;
+    }
+
+    Ok(inserted)
+}
+
+/// Run a `webhooks` subcommand
+pub async fn run(database_url: Url, command: WebhookCommand) -> Result<()> {
+    let mut conn = AsyncPgConnection::establish(database_url.as_str())
+        .await
+        .context("Failed to connect to the database")?;
+
+    match command {
+        WebhookCommand::Add(args) => {
+            let subscription = StoredWebhookSubscription {
+                url: args.url.to_string(),
+                secret: args.secret,
+                event_type: args.event_type,
+                seller: args.seller.map(|seller| seller.to_string()),
+                datapod_id: args.datapod_id.map(|id| normalize_id(&id).into_owned()),
+            };
+            let id: i64 = diesel::insert_into(webhook_subscriptions::table)
+                .values(&subscription)
+                .returning(webhook_subscriptions::id)
+                .get_result(&mut conn)
+                .await?;
+            println!("{id}");
+        }
+
+        WebhookCommand::List => {
+            use webhook_subscriptions::dsl::*;
+            let rows: Vec<(i64, String, Option<String>, Option<String>, Option<String>, bool)> =
+                webhook_subscriptions
+                    .select((id, url, event_type, seller, datapod_id, active))
+                    .order(id)
+                    .load(&mut conn)
+                    .await?;
+
+            let any = |filter: Option<String>| filter.unwrap_or_else(|| "*".to_string());
+            for (sub_id, sub_url, sub_event_type, sub_seller, sub_datapod_id, sub_active) in rows {
+                println!(
+                    "{sub_id}\t{sub_url}\tevent_type={}\tseller={}\tdatapod_id={}{}",
+                    any(sub_event_type),
+                    any(sub_seller),
+                    any(sub_datapod_id),
+                    if sub_active { "" } else { "\tinactive" },
+                );
+            }
+        }
+
+        WebhookCommand::Remove { id } => {
+            let removed = diesel::delete(webhook_subscriptions::table.find(id))
+                .execute(&mut conn)
+                .await?;
+            if removed == 0 {
+                bail!("No webhook subscription with ID {id}");
+            }
+        }
+
+        WebhookCommand::Retry { ids } => {
+            use webhook_deliveries::dsl::*;
+            let dead = webhook_deliveries.filter(status.eq(DEAD));
+            let requeue = (status.eq(PENDING), attempts.eq(0), next_attempt_at.eq(now));
+            let requeued = if ids.is_empty() {
+                diesel::update(dead).set(requeue).execute(&mut conn).await?
+            } else {
+                diesel::update(dead.filter(id.eq_any(ids)))
+                    .set(requeue)
+                    .execute(&mut conn)
+                    .await?
+            };
+            println!("Requeued {requeued} dead deliveries");
+        }
+
+        WebhookCommand::Deliver(args) => deliver(&mut conn, &args).await?,
+    }
+
+    Ok(())
+}
+
+/// A delivery claimed by this worker, with the subscription it goes to
+#[derive(QueryableByName, Debug)]
+struct ClaimedDelivery {
+    #[diesel(sql_type = BigInt)]
+    id: i64,
+    #[diesel(sql_type = Text)]
+    event_type: String,
+    #[diesel(sql_type = Jsonb)]
+    payload: serde_json::Value,
+    #[diesel(sql_type = Integer)]
+    attempts: i32,
+    #[diesel(sql_type = Text)]
+    url: String,
+    #[diesel(sql_type = Text)]
+    secret: String,
+}
+
+/// Deliver due webhooks until the process is stopped. Several workers may run at once: each
+/// claims deliveries by pushing their `next_attempt_at` past the request timeout, so a delivery
+/// whose worker died is picked up again once its claim lapses.
+async fn deliver(conn: &mut AsyncPgConnection, args: &DeliverArgs) -> Result<()> {
+    let timeout = Duration::from_millis(args.request_timeout_ms);
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    // Only deliveries to active subscriptions are claimed, so that those of an inactive one do
+    // not fill every batch. The status is spelled out rather than bound, which lets the planner
+    // use the partial index on due deliveries.
+    let claim = format!(
+        "UPDATE webhook_deliveries d \
+         SET next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => $2) \
+         FROM webhook_subscriptions s \
+         WHERE s.id = d.subscription_id \
+           AND d.id IN ( \
+               SELECT due.id FROM webhook_deliveries due \
+               JOIN webhook_subscriptions sub ON sub.id = due.subscription_id \
+               WHERE due.status = '{PENDING}' \
+                 AND due.next_attempt_at <= CURRENT_TIMESTAMP \
+                 AND sub.active \
+               ORDER BY due.next_attempt_at, due.id \
+               LIMIT $1 \
+               FOR UPDATE OF due SKIP LOCKED \
+           ) \
+         RETURNING d.id, d.event_type, d.payload, d.attempts, s.url, s.secret"
+    );
+
+    loop {
+        let claimed: Vec<ClaimedDelivery> = diesel::sql_query(claim.as_str())
+            .bind::<BigInt, _>(args.batch_size)
+            .bind::<Double, _>(2.0 * timeout.as_secs_f64())
+            .load(conn)
+            .await?;
+
+        if claimed.is_empty() {
+            tokio::time::sleep(Duration::from_millis(args.poll_interval_ms)).await;
+            continue;
+        }
+
+        let outcomes = futures::future::join_all(
+            claimed.iter().map(|delivery| attempt(&client, delivery, args)),
+        )
+        .await;
+
+        for (delivery, outcome) in claimed.iter().zip(outcomes) {
+            use webhook_deliveries::dsl::*;
+            let target = webhook_deliveries.find(delivery.id);
+            let attempt = delivery.attempts + 1;
+
+            match outcome {
+                Outcome::Delivered => {
+                    diesel::update(target)
+                        .set((
+                            status.eq(DELIVERED),
+                            attempts.eq(attempt),
+                            last_error.eq(None::<String>),
+                            delivered_at.eq(now),
+                        ))
+                        .execute(conn)
+                        .await?;
+                }
+                Outcome::Retry(delay, error) => {
+                    let delay = i64::try_from(delay.as_micros())
+                        .with_context(|| format!("Retry delay of {delay:?} is out of range"))?;
+                    diesel::update(target)
+                        .set((
+                            status.eq(PENDING),
+                            attempts.eq(attempt),
+                            last_error.eq(error),
+                            next_attempt_at.eq(now + delay.microseconds()),
+                        ))
+                        .execute(conn)
+                        .await?;
+                }
+                Outcome::Dead(error) => {
+                    diesel::update(target)
+                        .set((status.eq(DEAD), attempts.eq(attempt), last_error.eq(error)))
+                        .execute(conn)
+                        .await?;
+                }
+            }
+        }
+    }
+}
+
+/// What becomes of a delivery after an attempt to send it
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Delivered,
+    /// Failed with the error, to be tried again after the delay
+    Retry(Duration, String),
+    /// Failed with the error on the last attempt allowed
+    Dead(String),
+}
+
+/// Send `delivery`, which has failed `delivery.attempts` times so far
+async fn attempt(
+    client: &reqwest::Client,
+    delivery: &ClaimedDelivery,
+    args: &DeliverArgs,
+) -> Outcome {
+    let attempt = delivery.attempts + 1;
+    match send(client, delivery).await {
+        Ok(()) => Outcome::Delivered,
+        Err(err) if attempt >= args.max_attempts => Outcome::Dead(format!("{err:#}")),
+        Err(err) => Outcome::Retry(backoff(args, attempt), format!("{err:#}")),
+    }
+}
+
+/// Delay before retrying a delivery that failed `attempt` times: doubling from the initial
+/// backoff, up to the maximum
+fn backoff(args: &DeliverArgs, attempt: i32) -> Duration {
+    let exponent = (attempt - 1).clamp(0, 30) as u32;
+    let delay = args.initial_backoff_ms.saturating_mul(1 << exponent);
+    Duration::from_millis(delay.min(args.max_backoff_ms))
+}
+
+async fn send(client: &reqwest::Client, delivery: &ClaimedDelivery) -> Result<()> {
+    let body = delivery.payload.to_string();
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    let response = client
+        .post(&delivery.url)
+        .header("content-type", "application/json")
+        .header("x-sourcenet-delivery", delivery.id.to_string())
+        .header("x-sourcenet-event", &delivery.event_type)
+        .header("x-sourcenet-timestamp", timestamp.to_string())
+        .header("x-sourcenet-signature", signature(&delivery.secret, timestamp, &body))
+        .body(body)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        bail!("Webhook endpoint responded with {status}");
+    }
+    Ok(())
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `"{timestamp}.{body}"` keyed by the secret
+fn signature(secret: &str, timestamp: u64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{extract::State, http::HeaderMap, http::StatusCode, routing::post, Router};
+
+    use super::*;
+
+    /// Local stand-in for a webhook endpoint, which answers the first `failures` requests with a
+    /// 500 and records every request
+    #[derive(Clone, Default)]
+    struct Endpoint {
+        failures: usize,
+        requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+    }
+
+    async fn receive(
+        State(endpoint): State<Endpoint>,
+        headers: HeaderMap,
+        body: String,
+    ) -> StatusCode {
+        let mut requests = endpoint.requests.lock().unwrap();
+        requests.push((headers, body));
+        if requests.len() <= endpoint.failures {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::OK
+        }
+    }
+
+    /// Serve `endpoint` on a free local port, returning its URL
+    async fn serve(endpoint: Endpoint) -> String {
+        let app = Router::new().route("/hook", post(receive)).with_state(endpoint);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{address}/hook")
+    }
+
+    fn args(max_attempts: i32) -> DeliverArgs {
+        DeliverArgs {
+            batch_size: 1,
+            poll_interval_ms: 10,
+            request_timeout_ms: 5_000,
+            max_attempts,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 5_000,
+        }
+    }
+
+    fn delivery(url: String) -> ClaimedDelivery {
+        ClaimedDelivery {
+            id: 7,
+            event_type: "PurchaseCreated".to_string(),
+            payload: serde_json::json!({ "purchase_id": "0x1", "price_sui": "5" }),
+            attempts: 0,
+            url,
+            secret: "s3cret".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_after_server_error_then_delivers_signed_event() {
+        let endpoint = Endpoint { failures: 1, ..Default::default() };
+        let mut delivery = delivery(serve(endpoint.clone()).await);
+        let client = reqwest::Client::new();
+        let args = args(3);
+
+        let outcome = attempt(&client, &delivery, &args).await;
+        assert!(
+            matches!(&outcome, Outcome::Retry(delay, error)
+                if *delay == Duration::from_secs(1) && error.contains("500")),
+            "{outcome:?}",
+        );
+
+        delivery.attempts = 1;
+        assert_eq!(attempt(&client, &delivery, &args).await, Outcome::Delivered);
+
+        let requests = endpoint.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for (headers, body) in requests.iter() {
+            assert_eq!(serde_json::from_str::<serde_json::Value>(body).unwrap(), delivery.payload);
+            assert_eq!(headers["content-type"], "application/json");
+            assert_eq!(headers["x-sourcenet-delivery"], "7");
+            assert_eq!(headers["x-sourcenet-event"], "PurchaseCreated");
+
+            let timestamp = headers["x-sourcenet-timestamp"].to_str().unwrap().parse().unwrap();
+            let expected = signature("s3cret", timestamp, body);
+            assert_eq!(headers["x-sourcenet-signature"], expected.as_str());
+        }
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_last_attempt() {
+        let endpoint = Endpoint { failures: usize::MAX, ..Default::default() };
+        let mut delivery = delivery(serve(endpoint.clone()).await);
+        delivery.attempts = 2;
+
+        let outcome = attempt(&reqwest::Client::new(), &delivery, &args(3)).await;
+        assert!(matches!(&outcome, Outcome::Dead(error) if error.contains("500")), "{outcome:?}");
+    }
+
+    #[test]
+    fn filters_compare_normalized_ids() {
+        let filter = SubscriptionFilter {
+            id: 1,
+            event_type: None,
+            seller: Some("0xABC".to_string()),
+            datapod_id: Some("listing-42".to_string()),
+        }
+        .normalized();
+
+        let event = EventDescription {
+            module: "purchase",
+            event_type: "PurchaseCreated",
+            entity_id: "0x1".parse().unwrap(),
+            seller: Some("0xabc".parse().unwrap()),
+            datapod_id: Some("listing-42".into()),
+            transaction_digest: "11111111111111111111111111111111".parse().unwrap(),
+            event_index: 0,
+            checkpoint_sequence_number: 0,
+            timestamp: 0,
+        };
+        assert!(filter.matches(&event));
+
+        let other = EventDescription { datapod_id: Some("listing-43".into()), ..event };
+        assert!(!filter.matches(&other));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let args = args(10);
+        let delays: Vec<_> = (0..=5).map(|attempt| backoff(&args, attempt).as_secs()).collect();
+        assert_eq!(delays, [1, 1, 2, 4, 5, 5]);
+        assert_eq!(backoff(&args, i32::MAX), Duration::from_secs(5));
+
+        let args = DeliverArgs {
+            initial_backoff_ms: u64::MAX / 2,
+            max_backoff_ms: u64::MAX,
+            ..args
+        };
+        assert_eq!(backoff(&args, 3), Duration::from_millis(u64::MAX));
+    }
+
+    #[test]
+    fn signatures_are_hmac_sha256_of_timestamp_and_body() {
+        assert_eq!(
+            signature("s3cret", 1_700_000_000, r#"{"purchase_id":"0x1"}"#),
+            "sha256=0c87c812274bc502d8c73759f3ee3beb309608282890348d556e83f4bd3465ca",
+        );
+        assert_eq!(
+            signature("", 0, ""),
+            "sha256=b849d5a581847b281957065739df36df2463d1977ea8d6e1e4e6cf33fadc68c3",
+        );
+        assert_ne!(signature("s3cret", 1, "{}"), signature("s3cret", 2, "{}"));
+        assert_ne!(signature("s3cret", 1, "{}"), signature("other", 1, "{}"));
+    }
+}